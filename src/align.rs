@@ -0,0 +1,54 @@
+use core::ops;
+use super::BitSet;
+
+macro_rules! impl_aligned {
+	($name:ident, $align:literal, $doc:literal) => {
+		#[doc = $doc]
+		#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+		#[repr(C, align($align))]
+		pub struct $name<T>(pub T);
+
+		impl<T> ops::Deref for $name<T> {
+			type Target = T;
+			#[inline]
+			fn deref(&self) -> &T {
+				&self.0
+			}
+		}
+		impl<T> ops::DerefMut for $name<T> {
+			#[inline]
+			fn deref_mut(&mut self) -> &mut T {
+				&mut self.0
+			}
+		}
+
+		impl<T: BitSet> BitSet for $name<T> {
+			impl_bitset!();
+		}
+	};
+}
+
+impl_aligned!(Aligned16, 16, "Aligns the container to 16 bytes, matching 128-bit simd registers.");
+impl_aligned!(Aligned32, 32, "Aligns the container to 32 bytes, matching 256-bit simd registers.");
+impl_aligned!(Aligned64, 64, "Aligns the container to 64 bytes, matching 512-bit simd registers and cache lines.");
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	use super::SimdBitSet;
+
+	let mut a = Aligned32([[0u32; 4]; 16]);
+	assert_eq!(&a as *const _ as usize % 32, 0);
+	assert_eq!(a.bit_align_offset(), 0);
+	a.bit_init(true);
+	assert!(a.bit_all());
+
+	let a = Aligned16([[0u64; 2]; 3]);
+	assert_eq!(a.as_ptr() as usize % 16, 0);
+	assert_eq!(a.bit_align_offset(), 0);
+
+	let a = Aligned64([[0u8; 32]; 2]);
+	assert_eq!(a.as_ptr() as usize % 64, 0);
+	assert_eq!(a[1..].bit_align_offset(), 0);
+}
@@ -0,0 +1,417 @@
+use core::ops;
+use super::{BitSet, Endian, LengthError};
+
+/// Fixed-size bitset of exactly `BITS` bits backed by `WORDS` 64-bit words.
+///
+/// `WORDS` must be the number of words needed to hold `BITS` bits, ie. `(BITS + 63) / 64`.
+/// Unlike the plain containers `bit_len()` returns `BITS` and the unused high bits of the last word are kept cleared,
+/// so operations like `bit_not` and `bit_all` only consider the logical bits.
+///
+/// ```
+/// use bitset_core::{BitArray, BitSet};
+///
+/// let mut bits = BitArray::<100, 2>::new();
+/// bits.bit_not();
+/// assert_eq!(bits.bit_len(), 100);
+/// assert_eq!(bits.bit_count(), 100);
+/// assert!(bits.bit_all());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BitArray<const BITS: usize, const WORDS: usize> {
+	words: [u64; WORDS],
+}
+
+impl<const BITS: usize, const WORDS: usize> BitArray<BITS, WORDS> {
+	/// Returns a bitset with all bits cleared.
+	///
+	/// Panics if `WORDS` is not the number of words needed to hold `BITS` bits.
+	#[inline]
+	pub fn new() -> BitArray<BITS, WORDS> {
+		assert!(WORDS * 64 >= BITS && WORDS * 64 < BITS + 64, "BitArray word count does not match the number of bits");
+		BitArray { words: [0; WORDS] }
+	}
+	/// Returns a bitset from its backing words, the bits beyond `BITS` are cleared.
+	///
+	/// Panics if `WORDS` is not the number of words needed to hold `BITS` bits.
+	#[inline]
+	pub fn from_words(words: [u64; WORDS]) -> BitArray<BITS, WORDS> {
+		let mut bits = BitArray::new();
+		bits.words = words;
+		bits.clear_padding();
+		bits
+	}
+	/// Returns the backing words.
+	#[inline]
+	pub fn words(&self) -> &[u64; WORDS] {
+		&self.words
+	}
+	/// Returns the backing words.
+	#[inline]
+	pub fn into_words(self) -> [u64; WORDS] {
+		self.words
+	}
+
+	#[inline]
+	fn clear_padding(&mut self) {
+		if BITS & 63 != 0 {
+			self.words[WORDS - 1] &= !0 >> (64 - (BITS & 63));
+		}
+	}
+}
+
+impl<const BITS: usize, const WORDS: usize> Default for BitArray<BITS, WORDS> {
+	#[inline]
+	fn default() -> BitArray<BITS, WORDS> {
+		BitArray::new()
+	}
+}
+
+impl<const BITS: usize, const WORDS: usize> BitSet for BitArray<BITS, WORDS> {
+	#[inline]
+	fn bit_len(&self) -> usize {
+		BITS
+	}
+	#[inline]
+	fn bit_init(&mut self, value: bool) -> &mut Self {
+		self.words.bit_init(value);
+		self.clear_padding();
+		self
+	}
+
+	#[inline]
+	fn bit_test(&self, bit: usize) -> bool {
+		bit < BITS && self.words.bit_test(bit)
+	}
+	#[inline]
+	fn bit_set(&mut self, bit: usize) -> &mut Self {
+		assert!(bit < BITS);
+		self.words.bit_set(bit);
+		self
+	}
+	#[inline]
+	fn bit_reset(&mut self, bit: usize) -> &mut Self {
+		assert!(bit < BITS);
+		self.words.bit_reset(bit);
+		self
+	}
+	#[inline]
+	fn bit_flip(&mut self, bit: usize) -> &mut Self {
+		assert!(bit < BITS);
+		self.words.bit_flip(bit);
+		self
+	}
+	#[inline]
+	fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+		assert!(bit < BITS);
+		self.words.bit_cond(bit, value);
+		self
+	}
+	#[inline]
+	fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+		debug_assert!(word * 64 + (bit_in_word as usize) < BITS, "bit index out of range");
+		self.words.bit_test_at(word, bit_in_word)
+	}
+	#[inline]
+	fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+		assert!(word * 64 + (bit_in_word as usize) < BITS);
+		self.words.bit_set_at(word, bit_in_word);
+		self
+	}
+	#[inline]
+	fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+		// Keeps the padding bits out of reach of the word level fill
+		assert!(range.end <= BITS);
+		self.words.bit_cond_range(range, value);
+		self
+	}
+
+	#[inline]
+	fn bit_all(&self) -> bool {
+		let mut full = BitArray::<BITS, WORDS>::new();
+		full.bit_init(true);
+		self.words == full.words
+	}
+	#[inline]
+	fn bit_any(&self) -> bool {
+		self.words.bit_any()
+	}
+
+	#[inline]
+	fn bit_eq(&self, rhs: &Self) -> bool {
+		self.words == rhs.words
+	}
+	#[inline]
+	fn bit_disjoint(&self, rhs: &Self) -> bool {
+		self.words.bit_disjoint(&rhs.words)
+	}
+	#[inline]
+	fn bit_subset(&self, rhs: &Self) -> bool {
+		self.words.bit_subset(&rhs.words)
+	}
+
+	#[inline]
+	fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_or(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_and(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_andnot(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_xor(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_not(&mut self) -> &mut Self {
+		self.words.bit_not();
+		self.clear_padding();
+		self
+	}
+	#[inline]
+	fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		self.words.bit_mask(&rhs.words, &mask.words);
+		self
+	}
+
+	#[inline]
+	fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+		self.words.bit_or_broadcast(word);
+		self.clear_padding();
+		self
+	}
+	#[inline]
+	fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+		self.words.bit_and_broadcast(word);
+		self
+	}
+	#[inline]
+	fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+		self.words.bit_xor_broadcast(word);
+		self.clear_padding();
+		self
+	}
+
+	#[inline]
+	fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_or_word_set(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_and_word_set(&rhs.words);
+		self
+	}
+	#[inline]
+	fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.words.bit_xor_word_set(&rhs.words);
+		self
+	}
+
+	#[inline]
+	fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+		assert!(range.end <= BITS);
+		self.words.bit_reverse_range(range);
+		self
+	}
+
+	#[inline]
+	fn bit_count(&self) -> usize {
+		self.words.bit_count()
+	}
+	#[inline]
+	fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+		self.words.bit_and_count_at_least(&rhs.words, threshold)
+	}
+	#[inline]
+	fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+		assert_eq!(weights.len(), BITS);
+		let mut result = 0;
+		for i in 0..WORDS {
+			// The padding bits are cleared so the intersection stays below BITS
+			let mut word = self.words[i] & rhs.words[i];
+			while word != 0 {
+				result += weights[i * 64 + word.trailing_zeros() as usize] as u64;
+				word &= word - 1;
+			}
+		}
+		result
+	}
+	#[inline]
+	fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+		self.words.bit_hamming_at_most(&rhs.words, max)
+	}
+	#[inline]
+	fn bit_count_up_to(&self, limit: usize) -> usize {
+		self.words.bit_count_up_to(limit)
+	}
+	#[inline]
+	fn bit_find_first_zero(&self) -> Option<usize> {
+		self.words.bit_find_first_zero().filter(|&bit| bit < BITS)
+	}
+	#[inline]
+	fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+		self.words.bit_find_next_zero(from).filter(|&bit| bit < BITS)
+	}
+	#[inline]
+	fn bit_select_high(&self, k: usize) -> Option<usize> {
+		self.words.bit_select_high(k)
+	}
+	#[inline]
+	fn bit_rank_table(&self, out: &mut [usize]) {
+		self.words.bit_rank_table(out)
+	}
+
+	#[inline]
+	fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+		self.words.bit_load_bytes(bytes, endian)?;
+		self.clear_padding();
+		Ok(())
+	}
+	#[inline]
+	fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+		self.words.bit_store_bytes(out, endian)
+	}
+
+	#[inline]
+	fn bit_nonzero_words(&self) -> usize {
+		self.words.bit_nonzero_words()
+	}
+	#[inline]
+	fn bit_first_nonzero_word(&self) -> Option<usize> {
+		self.words.bit_first_nonzero_word()
+	}
+	#[inline]
+	fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+		self.words.bit_fold_words(init, f)
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let mut bits = BitArray::<100, 2>::new();
+	assert_eq!(bits.bit_len(), 100);
+	assert!(bits.bit_none());
+	assert_eq!(bits.bit_find_first_zero(), Some(0));
+
+	bits.bit_init(true);
+	assert!(bits.bit_all());
+	assert_eq!(bits.bit_count(), 100);
+	assert_eq!(bits.bit_leading_ones(), 100);
+	assert_eq!(bits.bit_find_first_zero(), None);
+	assert_eq!(bits.words(), &[!0, 0xf_ffff_ffff]);
+
+	bits.bit_reset(99);
+	assert!(!bits.bit_all());
+	assert_eq!(bits.bit_find_first_zero(), Some(99));
+	bits.bit_not();
+	assert_eq!(bits.words(), &[0, 0x8_0000_0000]);
+	assert_eq!(bits.bit_count(), 1);
+
+	bits.bit_xor_broadcast(!0);
+	assert_eq!(bits.bit_count(), 99);
+	assert!(!bits.bit_test(100));
+
+	let bits = BitArray::<100, 2>::from_words([1, !0]);
+	assert_eq!(bits.bit_count(), 37);
+	assert_eq!(bits.bit_summary().last, Some(99));
+	let mut bits = bits;
+	bits.bit_reverse_range(0..100);
+	assert_eq!(bits.bit_summary().first, Some(0));
+	assert!(bits.bit_test(99));
+
+	let mut bits = BitArray::<128, 2>::new();
+	bits.bit_not();
+	assert!(bits.bit_all());
+	assert_eq!(bits.bit_count(), 128);
+}
+
+#[test]
+fn test_padding() {
+	type Bits = BitArray<100, 2>;
+	#[track_caller]
+	fn check(bits: &mut Bits) -> &mut Bits {
+		assert_eq!(bits.words()[1] >> 36, 0, "padding bits must stay cleared");
+		bits
+	}
+	let full = *Bits::new().bit_init(true);
+	let pattern = Bits::from_words([0x5555_5555_5555_5555, !0]);
+	assert_eq!(pattern.words()[1], 0xf_ffff_ffff);
+
+	let mut bits = pattern;
+	check(bits.bit_init(true));
+	check(bits.bit_set_all());
+	check(bits.bit_clear_all().bit_not());
+	check(bits.bit_not_cond(true).bit_not_cond(true));
+	check(bits.bit_or_broadcast(!0).bit_xor_broadcast(0x8000_0000_0000_0001));
+	check(bits.bit_and_broadcast(!0).bit_set_range(0..100));
+	check(bits.bit_cond_range(50..100, true).bit_set_checked(100).bit_set_bits(&[0, 99]));
+	check(bits.bit_or(&full).bit_xor(&pattern).bit_and(&full).bit_andnot(&pattern));
+	check(bits.bit_mask(&full, &pattern).bit_mask_inv(&full, &pattern).bit_or_and(&full, &full));
+	check(bits.bit_or_tiled(&full).bit_xor_tiled(&pattern).bit_and_tiled(&full));
+	check(bits.bit_init(true).bit_rotate_left_range(0..100, 37).bit_shl_len(3, 100));
+	check(bits.bit_shl_fill(37, true).bit_shr_fill(5, true).bit_shl_fill(1, false));
+	check(bits.bit_init(true).bit_reverse_range(0..100).bit_reverse_groups(4).bit_swap_ranges(0, 50, 50));
+	check(bits.bit_blend_into(&full, &pattern, &full).bit_combine_into(&full, &pattern, super::BitOp::Xor));
+	bits.bit_or_delta(&full);
+	bits.bit_test_and_flip(99);
+	check(&mut bits);
+	bits.bit_load_bytes(&[0xff; 16], Endian::Little).unwrap();
+	check(&mut bits);
+	assert_eq!(bits.bit_count(), 100);
+
+	// The word set operations need a single word
+	let mut word = BitArray::<40, 1>::new();
+	word.bit_or_word_set(&BitArray::from_words([!0])).bit_xor_word_set(&BitArray::from_words([0x5555])).bit_and_word_set(&BitArray::from_words([!0]));
+	assert_eq!(word.words(), &[0xff_ffff_aaaa]);
+}
+
+#[test]
+fn test_weighted_overlap() {
+	let mut weights = [0u32; 100];
+	for (i, weight) in weights.iter_mut().enumerate() {
+		*weight = i as u32 + 1;
+	}
+	let a = BitArray::<100, 2>::from_words([0x8000_0000_0000_0011, !0]);
+	let b = BitArray::<100, 2>::from_words([0x8000_0000_0000_0101, 1 << 35 | 1]);
+	assert_eq!(a.bit_weighted_overlap(&b, &weights), 1 + 64 + 65 + 100);
+	assert_eq!(a.bit_weighted_overlap(&BitArray::new(), &weights), 0);
+	let reference = (0..100).filter(|&i| a.bit_test(i)).map(|i| weights[i] as u64).sum::<u64>();
+	assert_eq!(a.bit_weighted_overlap(&a, &weights), reference);
+}
+
+#[test]
+#[should_panic]
+fn test_weighted_overlap_len() {
+	let a = BitArray::<100, 2>::new();
+	a.bit_weighted_overlap(&a, &[0; 128]);
+}
+
+#[test]
+#[should_panic]
+fn test_word_set_len() {
+	BitArray::<100, 2>::new().bit_or_word_set(&BitArray::new());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "bit index out of range")]
+fn test_test_at_padding() {
+	// Bit 100 lives in the padding of the last word
+	let _ = BitArray::<100, 2>::new().bit_test_at(1, 36);
+}
+
+#[test]
+#[should_panic]
+fn test_word_count() {
+	let _ = BitArray::<100, 1>::new();
+}
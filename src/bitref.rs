@@ -0,0 +1,131 @@
+use super::BitSet;
+
+/// Read-only view of exactly `bit_len` bits packed into borrowed bytes.
+///
+/// Bit `i` is bit `i % 8` of byte `i / 8`, the same layout as the `[u8]` bitset.
+/// Unlike the plain `[u8]` bitset the bits of the last byte beyond `bit_len` are ignored,
+/// complementing `BitArray` for borrowed data of arbitrary bit length.
+///
+/// ```
+/// use bitset_core::BitRef;
+///
+/// // 37 bits with garbage in the unused high bits of the last byte
+/// let field = BitRef::new(&[0xff, 0xff, 0xff, 0xff, 0xfd], 37);
+/// assert_eq!(field.bit_len(), 37);
+/// assert_eq!(field.bit_count(), 36);
+/// assert!(!field.bit_all());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct BitRef<'a> {
+	bytes: &'a [u8],
+	bit_len: usize,
+}
+
+impl<'a> BitRef<'a> {
+	/// Returns a view of the first `bit_len` bits of the bytes.
+	///
+	/// Panics if the bytes hold fewer than `bit_len` bits.
+	#[inline]
+	pub fn new(bytes: &'a [u8], bit_len: usize) -> BitRef<'a> {
+		assert!(bit_len <= bytes.len() * 8, "BitRef length exceeds the bytes");
+		BitRef { bytes, bit_len }
+	}
+	/// Returns the underlying bytes, including any unused bits.
+	#[inline]
+	pub fn bytes(&self) -> &'a [u8] {
+		self.bytes
+	}
+
+	// Splits into the whole bytes and the masked partial last byte
+	#[inline]
+	fn split(&self) -> (&'a [u8], u8, u8) {
+		let full = self.bit_len / 8;
+		let mask = !(!0u8 << (self.bit_len % 8) as u32);
+		let tail = if mask != 0 { self.bytes[full] & mask } else { 0 };
+		(&self.bytes[..full], tail, mask)
+	}
+
+	/// Returns the number of bits.
+	#[inline]
+	pub fn bit_len(&self) -> usize {
+		self.bit_len
+	}
+	/// Returns if the given bit is set, bits beyond `bit_len` read as cleared.
+	#[inline]
+	pub fn bit_test(&self, bit: usize) -> bool {
+		bit < self.bit_len && self.bytes.bit_test(bit)
+	}
+	/// Returns if all bits are set.
+	///
+	/// Vacuously true for zero bits.
+	#[inline]
+	pub fn bit_all(&self) -> bool {
+		let (full, tail, mask) = self.split();
+		full.bit_all() && tail == mask
+	}
+	/// Returns if any bits are set.
+	#[inline]
+	pub fn bit_any(&self) -> bool {
+		let (full, tail, _) = self.split();
+		full.bit_any() || tail != 0
+	}
+	/// Returns if none of the bits are set.
+	#[inline]
+	pub fn bit_none(&self) -> bool {
+		!self.bit_any()
+	}
+	/// Counts the number of set bits.
+	#[inline]
+	pub fn bit_count(&self) -> usize {
+		let (full, tail, _) = self.split();
+		full.bit_count() + tail.count_ones() as usize
+	}
+	/// Returns if the two views hold the same bits, ignoring the unused bits.
+	///
+	/// Panics if the lengths differ.
+	#[inline]
+	pub fn bit_eq(&self, rhs: &BitRef<'_>) -> bool {
+		assert_eq!(self.bit_len, rhs.bit_len);
+		let (full, tail, _) = self.split();
+		let (rhs_full, rhs_tail, _) = rhs.split();
+		full == rhs_full && tail == rhs_tail
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	// 37 bits with garbage in the unused bits
+	let ones = [0xff, 0xff, 0xff, 0xff, 0xff];
+	let field = BitRef::new(&ones, 37);
+	assert_eq!(field.bit_count(), 37);
+	assert!(field.bit_all());
+	assert!(field.bit_any());
+	assert!(field.bit_test(36));
+	assert!(!field.bit_test(37));
+
+	let sparse = [0x00, 0x00, 0x00, 0x00, 0xe0];
+	let field = BitRef::new(&sparse, 37);
+	assert_eq!(field.bit_count(), 0);
+	assert!(field.bit_none());
+	assert!(!field.bit_all());
+	assert!(field.bit_eq(&BitRef::new(&[0; 5], 37)));
+	assert!(!field.bit_eq(&BitRef::new(&[0, 0, 0, 0, 0x10], 37)));
+
+	let bytes = [0x81, 0x42, 0x24];
+	let field = BitRef::new(&bytes, 24);
+	assert_eq!(field.bit_count(), bytes[..].bit_count());
+	assert!(field.bit_eq(&BitRef::new(&bytes, 24)));
+
+	let empty = BitRef::new(&[], 0);
+	assert!(empty.bit_all());
+	assert!(!empty.bit_any());
+	assert_eq!(empty.bit_count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_too_long() {
+	let _ = BitRef::new(&[0; 4], 33);
+}
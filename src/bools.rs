@@ -0,0 +1,241 @@
+use super::{BitSet, Endian, LengthError};
+
+// Every element is a backing word holding a single bit.
+impl BitSet for [bool] {
+	#[inline]
+	fn bit_len(&self) -> usize {
+		self.len()
+	}
+	#[inline]
+	fn bit_init(&mut self, value: bool) -> &mut Self {
+		self.fill(value);
+		self
+	}
+	#[inline]
+	fn bit_test(&self, bit: usize) -> bool {
+		self[bit]
+	}
+	#[inline]
+	fn bit_set(&mut self, bit: usize) -> &mut Self {
+		self[bit] = true;
+		self
+	}
+	#[inline]
+	fn bit_reset(&mut self, bit: usize) -> &mut Self {
+		self[bit] = false;
+		self
+	}
+	#[inline]
+	fn bit_flip(&mut self, bit: usize) -> &mut Self {
+		self[bit] = !self[bit];
+		self
+	}
+	#[inline]
+	fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+		self[bit] = value;
+		self
+	}
+	#[inline]
+	fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+		debug_assert_eq!(bit_in_word, 0);
+		self[word]
+	}
+	#[inline]
+	fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+		debug_assert_eq!(bit_in_word, 0);
+		self[word] = true;
+		self
+	}
+
+	#[inline]
+	fn bit_all(&self) -> bool {
+		self.iter().all(|&x| x)
+	}
+	#[inline]
+	fn bit_any(&self) -> bool {
+		self.iter().any(|&x| x)
+	}
+
+	#[inline]
+	fn bit_eq(&self, rhs: &Self) -> bool {
+		assert_eq!(self.len(), rhs.len());
+		self == rhs
+	}
+	#[inline]
+	fn bit_disjoint(&self, rhs: &Self) -> bool {
+		assert_eq!(self.len(), rhs.len());
+		self.iter().zip(rhs).all(|(&x, &y)| !(x & y))
+	}
+	#[inline]
+	fn bit_subset(&self, rhs: &Self) -> bool {
+		assert_eq!(self.len(), rhs.len());
+		self.iter().zip(rhs).all(|(&x, &y)| !x | y)
+	}
+
+	#[inline]
+	fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(self.len(), rhs.len());
+		for (x, &y) in self.iter_mut().zip(rhs) {
+			*x |= y;
+		}
+		self
+	}
+	#[inline]
+	fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(self.len(), rhs.len());
+		for (x, &y) in self.iter_mut().zip(rhs) {
+			*x &= y;
+		}
+		self
+	}
+	#[inline]
+	fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(self.len(), rhs.len());
+		for (x, &y) in self.iter_mut().zip(rhs) {
+			*x &= !y;
+		}
+		self
+	}
+	#[inline]
+	fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(self.len(), rhs.len());
+		for (x, &y) in self.iter_mut().zip(rhs) {
+			*x ^= y;
+		}
+		self
+	}
+	#[inline]
+	fn bit_not(&mut self) -> &mut Self {
+		for x in self.iter_mut() {
+			*x = !*x;
+		}
+		self
+	}
+	#[inline]
+	fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		assert_eq!(self.len(), rhs.len());
+		assert_eq!(self.len(), mask.len());
+		for ((x, &y), &m) in self.iter_mut().zip(rhs).zip(mask) {
+			*x = if m { y } else { *x };
+		}
+		self
+	}
+
+	#[inline]
+	fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+		if word & 1 != 0 {
+			self.fill(true);
+		}
+		self
+	}
+	#[inline]
+	fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+		if word & 1 == 0 {
+			self.fill(false);
+		}
+		self
+	}
+	#[inline]
+	fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+		if word & 1 != 0 {
+			self.bit_not();
+		}
+		self
+	}
+
+	#[inline]
+	fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.len(), 1);
+		self.bit_or_broadcast(rhs[0] as u64)
+	}
+	#[inline]
+	fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.len(), 1);
+		self.bit_and_broadcast(rhs[0] as u64)
+	}
+	#[inline]
+	fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.len(), 1);
+		self.bit_xor_broadcast(rhs[0] as u64)
+	}
+
+	#[inline]
+	fn bit_count(&self) -> usize {
+		self.iter().filter(|&&x| x).count()
+	}
+	#[inline]
+	fn bit_rank_table(&self, out: &mut [usize]) {
+		assert_eq!(out.len(), self.len() + 1);
+		let mut rank = 0;
+		for (entry, &x) in out.iter_mut().zip(self) {
+			*entry = rank;
+			rank += x as usize;
+		}
+		out[self.len()] = rank;
+	}
+
+	#[inline]
+	fn bit_load_bytes(&mut self, bytes: &[u8], _endian: Endian) -> Result<(), LengthError> {
+		if bytes.len() != self.len() {
+			return Err(LengthError);
+		}
+		for (x, &byte) in self.iter_mut().zip(bytes) {
+			*x = byte != 0;
+		}
+		Ok(())
+	}
+	#[inline]
+	fn bit_store_bytes(&self, out: &mut [u8], _endian: Endian) -> Result<(), LengthError> {
+		if out.len() != self.len() {
+			return Err(LengthError);
+		}
+		for (byte, &x) in out.iter_mut().zip(self) {
+			*byte = x as u8;
+		}
+		Ok(())
+	}
+
+	#[inline]
+	fn bit_nonzero_words(&self) -> usize {
+		self.bit_count()
+	}
+	#[inline]
+	fn bit_first_nonzero_word(&self) -> Option<usize> {
+		self.iter().position(|&x| x)
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let mut bools = [false; 37];
+	super::unary_tests(&mut bools[..]);
+	super::unary_tests(&mut [false; 64][..]);
+
+	let mut bools = [false, true, true, false, true];
+	assert_eq!(bools.bit_count(), 3);
+	assert!(bools.bit_subset(&[true, true, true, false, true]));
+	assert!(bools.bit_disjoint(&[true, false, false, true, false]));
+	bools.bit_xor(&[true; 5]);
+	assert_eq!(bools, [true, false, false, true, false]);
+	assert_eq!(format!("{}", bools.bit_fmt()), "10010");
+	assert_eq!(format!("{:x}", bools.bit_fmt()), "90");
+
+	bools.bit_xor_word_set(&[true]);
+	assert_eq!(bools, [false, true, true, false, true]);
+	bools.bit_or_word_set(&[false]).bit_and_word_set(&[true]);
+	assert_eq!(bools.bit_count(), 3);
+}
+
+#[test]
+#[should_panic]
+fn test_andnot_changed_len() {
+	[true; 8].bit_andnot_changed(&[true; 9]);
+}
+
+#[test]
+#[should_panic]
+fn test_word_set_len() {
+	[false; 8].bit_or_word_set(&[true, true]);
+}
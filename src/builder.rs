@@ -0,0 +1,78 @@
+use core::ops;
+use super::BitSet;
+
+/// Chainable construction of a bitset from ranges, individual and conditional bits.
+///
+/// Returned by `BitSet::bit_builder`.
+///
+/// ```
+/// use bitset_core::BitSet;
+///
+/// let bits = 0u64.bit_builder().set_range(4..8).set(33).set_if(40, false).from_indices(vec![50, 60]).build();
+/// assert_eq!(bits, 0x1004_0002_0000_00f0);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitBuilder<T> {
+	bits: T,
+}
+
+impl<T: BitSet> BitBuilder<T> {
+	/// Starts building from the given bitset.
+	#[inline]
+	pub fn new(bits: T) -> BitBuilder<T> {
+		BitBuilder { bits }
+	}
+	/// Sets the given bit.
+	#[inline]
+	pub fn set(mut self, bit: usize) -> BitBuilder<T> {
+		self.bits.bit_set(bit);
+		self
+	}
+	/// Sets all the bits in the range.
+	#[inline]
+	pub fn set_range(mut self, range: ops::Range<usize>) -> BitBuilder<T> {
+		self.bits.bit_cond_range(range, true);
+		self
+	}
+	/// Sets the given bit if `cond` is true.
+	#[inline]
+	pub fn set_if(mut self, bit: usize, cond: bool) -> BitBuilder<T> {
+		if cond {
+			self.bits.bit_set(bit);
+		}
+		self
+	}
+	/// Sets all the bits yielded by the iterator.
+	#[inline]
+	pub fn from_indices<I: IntoIterator<Item = usize>>(mut self, indices: I) -> BitBuilder<T> {
+		for bit in indices {
+			self.bits.bit_set(bit);
+		}
+		self
+	}
+	/// Returns the built bitset.
+	#[inline]
+	pub fn build(self) -> T {
+		self.bits
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let bits = 0u64.bit_builder()
+		.set_range(10..20)
+		.set(3)
+		.set(63)
+		.set_if(30, true)
+		.set_if(31, false)
+		.build();
+
+	let mut expected = 0u64;
+	expected.bit_cond_range(10..20, true).bit_set(3).bit_set(63).bit_set(30);
+	assert_eq!(bits, expected);
+
+	let bits = BitBuilder::new(0u16).from_indices(0..16).build();
+	assert!(bits.bit_all());
+}
@@ -0,0 +1,84 @@
+use core::cell::Cell;
+use super::BitSet;
+
+/// Single bit operations on integers behind a `Cell`.
+///
+/// `BitSet` takes `&mut self` to modify the bits, these methods go through `Cell::get` and `Cell::set` instead
+/// so flag words can be shared without the borrow checker getting in the way.
+///
+/// ```
+/// use core::cell::Cell;
+/// use bitset_core::CellBitSet;
+///
+/// let flags = Cell::new(0u32);
+/// let alias = &flags;
+/// flags.bit_set(3);
+/// alias.bit_flip(4);
+/// assert!(flags.bit_test(4));
+/// assert_eq!(flags.get(), 0x18);
+/// ```
+pub trait CellBitSet {
+	/// Returns if the given bit is set.
+	fn bit_test(&self, bit: usize) -> bool;
+	/// Sets the given bit.
+	fn bit_set(&self, bit: usize) -> &Self;
+	/// Resets the given bit.
+	fn bit_reset(&self, bit: usize) -> &Self;
+	/// Flips the given bit.
+	fn bit_flip(&self, bit: usize) -> &Self;
+}
+
+macro_rules! impl_cell_bit_set {
+	($ty:ty) => {
+		impl CellBitSet for Cell<$ty> {
+			#[inline]
+			fn bit_test(&self, bit: usize) -> bool {
+				self.get().bit_test(bit)
+			}
+			#[inline]
+			fn bit_set(&self, bit: usize) -> &Self {
+				self.set(*self.get().bit_set(bit));
+				self
+			}
+			#[inline]
+			fn bit_reset(&self, bit: usize) -> &Self {
+				self.set(*self.get().bit_reset(bit));
+				self
+			}
+			#[inline]
+			fn bit_flip(&self, bit: usize) -> &Self {
+				self.set(*self.get().bit_flip(bit));
+				self
+			}
+		}
+	};
+}
+
+impl_cell_bit_set!(u8);
+impl_cell_bit_set!(u16);
+impl_cell_bit_set!(u32);
+impl_cell_bit_set!(u64);
+impl_cell_bit_set!(u128);
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let flags = Cell::new(0u32);
+	let alias = &flags;
+	for bit in 0..32 {
+		flags.bit_set(bit);
+		assert!(alias.bit_test(bit));
+		alias.bit_reset(bit);
+		assert!(!flags.bit_test(bit));
+		flags.bit_flip(bit).bit_flip(bit);
+	}
+	assert_eq!(flags.get(), 0);
+
+	flags.bit_set(0).bit_set(31).bit_flip(5).bit_reset(0);
+	assert_eq!(flags.get(), 0x8000_0020);
+
+	let wide = Cell::new(0u128);
+	wide.bit_set(127);
+	assert_eq!(wide.get(), 1 << 127);
+}
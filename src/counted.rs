@@ -0,0 +1,404 @@
+use core::{cmp, ops};
+use super::{BitOp, BitSet, Endian, LengthError};
+
+/// Wrapper caching the number of set bits.
+///
+/// The single bit methods adjust the cached count, the bulk operations recount the bits afterwards.
+/// This makes `bit_count` constant time for workloads which query the count more often than they modify the bits.
+///
+/// ```
+/// use bitset_core::{BitArray, BitSet, CountedBitSet};
+///
+/// let mut bits = CountedBitSet::new(BitArray::<100, 2>::new());
+/// bits.bit_set(3).bit_set(99).bit_flip(3);
+/// assert_eq!(bits.bit_count(), 1);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CountedBitSet<T> {
+	bits: T,
+	count: usize,
+}
+
+impl<T: BitSet> CountedBitSet<T> {
+	/// Wraps the bitset, counting its set bits.
+	#[inline]
+	pub fn new(bits: T) -> CountedBitSet<T> {
+		let count = bits.bit_count();
+		CountedBitSet { bits, count }
+	}
+	/// Returns the wrapped bitset.
+	#[inline]
+	pub fn inner(&self) -> &T {
+		&self.bits
+	}
+	/// Unwraps the bitset.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		self.bits
+	}
+
+	#[inline]
+	fn recount(&mut self) -> &mut Self {
+		self.count = self.bits.bit_count();
+		self
+	}
+}
+
+impl<T: BitSet> BitSet for CountedBitSet<T> {
+	#[inline]
+	fn bit_len(&self) -> usize {
+		self.bits.bit_len()
+	}
+	#[inline]
+	fn bit_init(&mut self, value: bool) -> &mut Self {
+		self.bits.bit_init(value);
+		self.count = if value { self.bits.bit_len() } else { 0 };
+		self
+	}
+	#[inline]
+	fn bit_test(&self, bit: usize) -> bool {
+		self.bits.bit_test(bit)
+	}
+	#[inline]
+	fn bit_set(&mut self, bit: usize) -> &mut Self {
+		self.count += !self.bits.bit_test(bit) as usize;
+		self.bits.bit_set(bit);
+		self
+	}
+	#[inline]
+	fn bit_reset(&mut self, bit: usize) -> &mut Self {
+		self.count -= self.bits.bit_test(bit) as usize;
+		self.bits.bit_reset(bit);
+		self
+	}
+	#[inline]
+	fn bit_flip(&mut self, bit: usize) -> &mut Self {
+		if self.bits.bit_test(bit) {
+			self.count -= 1;
+		}
+		else {
+			self.count += 1;
+		}
+		self.bits.bit_flip(bit);
+		self
+	}
+	#[inline]
+	fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+		if value {
+			self.bit_set(bit)
+		}
+		else {
+			self.bit_reset(bit)
+		}
+	}
+	#[inline]
+	fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+		self.bits.bit_test_at(word, bit_in_word)
+	}
+	#[inline]
+	fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+		self.count += !self.bits.bit_test_at(word, bit_in_word) as usize;
+		self.bits.bit_set_at(word, bit_in_word);
+		self
+	}
+	#[inline]
+	fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+		self.bits.bit_cond_range(range, value);
+		self.recount()
+	}
+	#[inline]
+	fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+		self.bits.bit_set_bits(indices);
+		self.recount()
+	}
+
+	#[inline]
+	fn bit_all(&self) -> bool {
+		self.count == self.bits.bit_len()
+	}
+	#[inline]
+	fn bit_any(&self) -> bool {
+		self.count != 0
+	}
+	#[inline]
+	fn bit_is_empty(&self) -> bool {
+		self.count == 0
+	}
+	#[inline]
+	fn bit_is_full(&self) -> bool {
+		self.count == self.bits.bit_len()
+	}
+
+	#[inline]
+	fn bit_eq(&self, rhs: &Self) -> bool {
+		self.count == rhs.count && self.bits.bit_eq(&rhs.bits)
+	}
+	#[inline]
+	fn bit_disjoint(&self, rhs: &Self) -> bool {
+		self.bits.bit_disjoint(&rhs.bits)
+	}
+	#[inline]
+	fn bit_subset(&self, rhs: &Self) -> bool {
+		self.count <= rhs.count && self.bits.bit_subset(&rhs.bits)
+	}
+	#[inline]
+	fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+		self.bits.bit_missing_from(&available.bits, &mut out.bits);
+		out.recount();
+	}
+
+	#[inline]
+	fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_or(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_and(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_andnot(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+		self.bits.bit_andnot_range(&rhs.bits, range);
+		self.recount()
+	}
+	#[inline]
+	fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+		let delta = self.bits.bit_or_delta(&rhs.bits);
+		self.count += delta;
+		delta
+	}
+	#[inline]
+	fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+		let delta = self.bits.bit_andnot_delta(&rhs.bits);
+		self.count -= delta;
+		delta
+	}
+	#[inline]
+	fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_xor(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_not(&mut self) -> &mut Self {
+		self.bits.bit_not();
+		self.count = self.bits.bit_len() - self.count;
+		self
+	}
+	#[inline]
+	fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+		if condition {
+			self.bit_not();
+		}
+		self
+	}
+	#[inline]
+	fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		self.bits.bit_mask(&rhs.bits, &mask.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		self.bits.bit_mask_inv(&rhs.bits, &mask.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+		self.bits.bit_or_and(&rhs.bits, &cond.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+		self.bits.bit_or_broadcast(word);
+		self.recount()
+	}
+	#[inline]
+	fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+		self.bits.bit_and_broadcast(word);
+		self.recount()
+	}
+	#[inline]
+	fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+		self.bits.bit_xor_broadcast(word);
+		self.recount()
+	}
+	#[inline]
+	fn bit_rotate_left_range(&mut self, range: ops::Range<usize>, n: usize) -> &mut Self {
+		self.bits.bit_rotate_left_range(range, n);
+		self
+	}
+	#[inline]
+	fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+		let len = self.bits.bit_len();
+		assert!(index < len);
+		self.count -= self.bits.bit_test(len - 1) as usize;
+		self.count += value as usize;
+		self.bits.bit_insert(index, value);
+		self
+	}
+	#[inline]
+	fn bit_remove(&mut self, index: usize) -> bool {
+		let removed = self.bits.bit_remove(index);
+		self.count -= removed as usize;
+		removed
+	}
+	#[inline]
+	fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+		self.bits.bit_shl_len(n, len);
+		self.recount()
+	}
+	#[inline]
+	fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+		self.bits.bit_shl_fill(n, fill);
+		self.recount()
+	}
+	#[inline]
+	fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+		self.bits.bit_shr_fill(n, fill);
+		self.recount()
+	}
+	#[inline]
+	fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+		self.bits.bit_reverse_range(range);
+		self
+	}
+	#[inline]
+	fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+		self.bits.bit_reverse_groups(group_bits);
+		self
+	}
+	#[inline]
+	fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+		self.bits.bit_swap_ranges(a, b, len);
+		self
+	}
+	#[inline]
+	fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+		self.bits.bit_combine_into(&a.bits, &b.bits, op);
+		self.recount()
+	}
+	#[inline]
+	fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+		self.bits.bit_and_tiled(&pattern.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+		self.bits.bit_or_tiled(&pattern.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+		self.bits.bit_xor_tiled(&pattern.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_or_word_set(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_and_word_set(&rhs.bits);
+		self.recount()
+	}
+	#[inline]
+	fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+		self.bits.bit_xor_word_set(&rhs.bits);
+		self.recount()
+	}
+
+	#[inline]
+	fn bit_count(&self) -> usize {
+		self.count
+	}
+	#[inline]
+	fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+		self.bits.bit_and_count_at_least(&rhs.bits, threshold)
+	}
+	#[inline]
+	fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+		self.bits.bit_weighted_overlap(&rhs.bits, weights)
+	}
+	#[inline]
+	fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+		// The difference in population is a lower bound on the distance
+		self.count.abs_diff(rhs.count) <= max && self.bits.bit_hamming_at_most(&rhs.bits, max)
+	}
+	#[inline]
+	fn bit_count_up_to(&self, limit: usize) -> usize {
+		cmp::min(self.count, limit)
+	}
+	#[inline]
+	fn bit_select_high(&self, k: usize) -> Option<usize> {
+		if k >= self.count {
+			return None;
+		}
+		self.bits.bit_select_high(k)
+	}
+	#[inline]
+	fn bit_rank_table(&self, out: &mut [usize]) {
+		self.bits.bit_rank_table(out)
+	}
+
+	#[inline]
+	fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+		self.bits.bit_load_bytes(bytes, endian)?;
+		self.recount();
+		Ok(())
+	}
+	#[inline]
+	fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+		self.bits.bit_store_bytes(out, endian)
+	}
+
+	#[inline]
+	fn bit_nonzero_words(&self) -> usize {
+		self.bits.bit_nonzero_words()
+	}
+	#[inline]
+	fn bit_first_nonzero_word(&self) -> Option<usize> {
+		self.bits.bit_first_nonzero_word()
+	}
+	#[inline]
+	fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+		self.bits.bit_fold_words(init, f)
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let mut random = super::xorshift(0x1234_5678);
+
+	let mut bits = CountedBitSet::new(0u128);
+	let other = CountedBitSet::new(0x0123_4567_89ab_cdef_0f0f_0f0f_f0f0_f0f0u128);
+	for _ in 0..1000 {
+		let bit = random() as usize % 128;
+		match random() % 12 {
+			0 | 1 => { bits.bit_set(bit); },
+			2 | 3 => { bits.bit_reset(bit); },
+			4 | 5 => { bits.bit_flip(bit); },
+			6 => { bits.bit_cond(bit, bit & 1 != 0); },
+			7 => { bits.bit_or(&other); },
+			8 => { bits.bit_and(&other); },
+			9 => { bits.bit_xor(&other); },
+			10 => { bits.bit_not(); },
+			_ => { bits.bit_cond_range(bit / 2..bit, random() & 1 != 0); },
+		}
+		assert_eq!(bits.bit_count(), bits.inner().bit_count());
+	}
+
+	// A u128 is a single backing word
+	let mut bits = CountedBitSet::new(0x0f0fu128);
+	bits.bit_or_word_set(&other).bit_xor_word_set(&CountedBitSet::new(!0));
+	assert_eq!(bits.bit_count(), 128 - (0x0f0f | other.inner()).count_ones() as usize);
+	bits.bit_and_word_set(&CountedBitSet::new(0));
+	assert_eq!(bits.bit_count(), 0);
+}
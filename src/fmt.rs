@@ -1,26 +1,108 @@
-use core::fmt;
+use core::{cmp, fmt};
 use super::BitSet;
 
 /// BitSet formatter.
 #[repr(transparent)]
 pub struct BitFmt<T: ?Sized>(T);
 
-fn bitstring<T: ?Sized + BitSet>(this: &T, f: &mut fmt::Formatter) -> fmt::Result {
+/// BitSet formatter displaying the highest bit first, see `BitFmt::msb_first`.
+#[repr(transparent)]
+pub struct BitFmtMsb<T: ?Sized>(T);
+
+impl<T: ?Sized> BitFmt<T> {
+	/// Displays the bits from the highest to the lowest index, the way binary integer literals are written.
+	///
+	/// The default `Display` prints bit 0 first. Groups of 8 bits are separated by `_` counting from bit 0 in both cases.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// assert_eq!(format!("{}", 0x0105u16.bit_fmt()), "10100000_10000000");
+	/// assert_eq!(format!("{}", 0x0105u16.bit_fmt().msb_first()), "00000001_00000101");
+	/// ```
+	#[inline]
+	pub fn msb_first(&self) -> &BitFmtMsb<T> {
+		unsafe { &*(self as *const _ as *const _) }
+	}
+	/// Limits the `Display` and hex output to at most `max_chars` characters followed by `…` when the full output would be longer.
+	///
+	/// Formatting stops as soon as the limit is reached, so large sets are cheap to log.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0xffu8; 1000];
+	/// assert_eq!(format!("{:x}", bits.bit_fmt().truncated(6)), "ffffff…");
+	/// assert_eq!(format!("{}", bits.bit_fmt().truncated(4).ascii()), "1111...");
+	/// assert_eq!(format!("{:x}", bits[..2].bit_fmt().truncated(6)), "ffff");
+	/// ```
+	#[inline]
+	pub fn truncated(&self, max_chars: usize) -> BitFmtTruncated<'_, T> {
+		BitFmtTruncated { bits: &self.0, max_chars, ascii: false }
+	}
+}
+
+/// BitSet formatter limiting the length of the output, see `BitFmt::truncated`.
+pub struct BitFmtTruncated<'a, T: ?Sized> {
+	bits: &'a T,
+	max_chars: usize,
+	ascii: bool,
+}
+
+impl<'a, T: ?Sized> BitFmtTruncated<'a, T> {
+	/// Marks the truncation with `...` instead of `…`.
+	#[inline]
+	pub fn ascii(self) -> BitFmtTruncated<'a, T> {
+		BitFmtTruncated { ascii: true, ..self }
+	}
+
+	fn write(&self, f: &mut fmt::Formatter, body: fn(&T, &mut Limit<'_, '_>) -> fmt::Result) -> fmt::Result {
+		let mut limit = Limit { f, remaining: self.max_chars, truncated: false };
+		match body(self.bits, &mut limit) {
+			// The limit stops the formatting early by failing the write
+			Err(_) if limit.truncated => limit.f.write_str(if self.ascii { "..." } else { "…" }),
+			result => result,
+		}
+	}
+}
+
+// Writes up to a number of ASCII characters, then fails and records the truncation
+struct Limit<'a, 'f> {
+	f: &'a mut fmt::Formatter<'f>,
+	remaining: usize,
+	truncated: bool,
+}
+
+impl<'a, 'f> fmt::Write for Limit<'a, 'f> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if s.len() <= self.remaining {
+			self.remaining -= s.len();
+			return self.f.write_str(s);
+		}
+		self.f.write_str(&s[..self.remaining])?;
+		self.remaining = 0;
+		self.truncated = true;
+		Err(fmt::Error)
+	}
+}
+
+fn bitstring<T: ?Sized + BitSet, W: fmt::Write>(this: &T, f: &mut W) -> fmt::Result {
 	const ALPHABET: [u8; 2] = [b'0', b'1'];
 	let mut buf = [0u8; 9];
 	let mut first = true;
 	buf[0] = b'_';
 	let mut i = 0;
 	while i < this.bit_len() {
-		buf[1] = ALPHABET[this.bit_test(i + 0) as usize];
-		buf[2] = ALPHABET[this.bit_test(i + 1) as usize];
-		buf[3] = ALPHABET[this.bit_test(i + 2) as usize];
-		buf[4] = ALPHABET[this.bit_test(i + 3) as usize];
-		buf[5] = ALPHABET[this.bit_test(i + 4) as usize];
-		buf[6] = ALPHABET[this.bit_test(i + 5) as usize];
-		buf[7] = ALPHABET[this.bit_test(i + 6) as usize];
-		buf[8] = ALPHABET[this.bit_test(i + 7) as usize];
-		let s = unsafe { &*((&buf[first as usize..]) as *const _ as *const str) };
+		buf[1] = ALPHABET[this.bit_get(i) as usize];
+		buf[2] = ALPHABET[this.bit_get(i + 1) as usize];
+		buf[3] = ALPHABET[this.bit_get(i + 2) as usize];
+		buf[4] = ALPHABET[this.bit_get(i + 3) as usize];
+		buf[5] = ALPHABET[this.bit_get(i + 4) as usize];
+		buf[6] = ALPHABET[this.bit_get(i + 5) as usize];
+		buf[7] = ALPHABET[this.bit_get(i + 6) as usize];
+		buf[8] = ALPHABET[this.bit_get(i + 7) as usize];
+		let n = cmp::min(8, this.bit_len() - i);
+		let s = unsafe { &*((&buf[first as usize..1 + n]) as *const _ as *const str) };
 		f.write_str(s)?;
 		i += 8;
 		first = false;
@@ -28,22 +110,49 @@ fn bitstring<T: ?Sized + BitSet>(this: &T, f: &mut fmt::Formatter) -> fmt::Resul
 	Ok(())
 }
 
+fn bitstring_msb<T: ?Sized + BitSet>(this: &T, f: &mut fmt::Formatter) -> fmt::Result {
+	const ALPHABET: [u8; 2] = [b'0', b'1'];
+	let mut buf = [0u8; 9];
+	let mut end = this.bit_len();
+	while end > 0 {
+		let start = (end - 1) & !7;
+		let mut n = 0;
+		for i in (start..end).rev() {
+			buf[n] = ALPHABET[this.bit_test(i) as usize];
+			n += 1;
+		}
+		if start > 0 {
+			buf[n] = b'_';
+			n += 1;
+		}
+		let s = unsafe { &*((&buf[..n]) as *const _ as *const str) };
+		f.write_str(s)?;
+		end = start;
+	}
+	Ok(())
+}
+
 const UPPERHEX_ALPHABET: [u8; 16] = [b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'A', b'B', b'C', b'D', b'E', b'F'];
 const LOWERHEX_ALPHABET: [u8; 16] = [b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'a', b'b', b'c', b'd', b'e', b'f'];
 
-fn hexstring<T: ?Sized + BitSet>(this: &T, f: &mut fmt::Formatter, alphabet: &[u8; 16]) -> fmt::Result {
+// Out of range bits of a final partial byte read as zero
+#[inline]
+fn hexbyte<T: ?Sized + BitSet>(this: &T, i: usize) -> u8 {
+	(this.bit_get(i) as u8) << 7 |
+	(this.bit_get(i + 1) as u8) << 6 |
+	(this.bit_get(i + 2) as u8) << 5 |
+	(this.bit_get(i + 3) as u8) << 4 |
+	(this.bit_get(i + 4) as u8) << 3 |
+	(this.bit_get(i + 5) as u8) << 2 |
+	(this.bit_get(i + 6) as u8) << 1 |
+	(this.bit_get(i + 7) as u8)
+}
+
+fn hexstring<T: ?Sized + BitSet, W: fmt::Write>(this: &T, f: &mut W, alphabet: &[u8; 16]) -> fmt::Result {
 	let mut buf = [0u8; 2];
 	let mut i = 0;
 	while i < this.bit_len() {
-		let byte =
-			(this.bit_test(i + 0) as u8) << 7 |
-			(this.bit_test(i + 1) as u8) << 6 |
-			(this.bit_test(i + 2) as u8) << 5 |
-			(this.bit_test(i + 3) as u8) << 4 |
-			(this.bit_test(i + 4) as u8) << 3 |
-			(this.bit_test(i + 5) as u8) << 2 |
-			(this.bit_test(i + 6) as u8) << 1 |
-			(this.bit_test(i + 7) as u8) << 0;
+		let byte = hexbyte(this, i);
 		buf[0] = alphabet[(byte >> 4) as usize];
 		buf[1] = alphabet[(byte & 0xf) as usize];
 		let s = unsafe { &*((&buf[..]) as *const _ as *const str) };
@@ -53,11 +162,47 @@ fn hexstring<T: ?Sized + BitSet>(this: &T, f: &mut fmt::Formatter, alphabet: &[u
 	Ok(())
 }
 
+pub(crate) fn hex_into<T: ?Sized + BitSet>(this: &T, out: &mut [u8]) -> usize {
+	let bits = this.bit_len();
+	let len = (bits / 8 + (bits & 7 != 0) as usize) * 2;
+	if out.len() < len {
+		return 0;
+	}
+	let mut i = 0;
+	while i < this.bit_len() {
+		let byte = hexbyte(this, i);
+		out[i / 4] = LOWERHEX_ALPHABET[(byte >> 4) as usize];
+		out[i / 4 + 1] = LOWERHEX_ALPHABET[(byte & 0xf) as usize];
+		i += 8;
+	}
+	len
+}
+
 impl<T: ?Sized + BitSet> fmt::Display for BitFmt<T> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		bitstring(&self.0, f)
 	}
 }
+impl<T: ?Sized + BitSet> fmt::Display for BitFmtMsb<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		bitstring_msb(&self.0, f)
+	}
+}
+impl<'a, T: ?Sized + BitSet> fmt::Display for BitFmtTruncated<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.write(f, |bits, f| bitstring(bits, f))
+	}
+}
+impl<'a, T: ?Sized + BitSet> fmt::UpperHex for BitFmtTruncated<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.write(f, |bits, f| hexstring(bits, f, &UPPERHEX_ALPHABET))
+	}
+}
+impl<'a, T: ?Sized + BitSet> fmt::LowerHex for BitFmtTruncated<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.write(f, |bits, f| hexstring(bits, f, &LOWERHEX_ALPHABET))
+	}
+}
 impl<T: ?Sized + BitSet> fmt::Debug for BitFmt<T> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.write_str("\"")?;
@@ -77,3 +222,52 @@ impl<T: ?Sized + BitSet> fmt::LowerHex for BitFmt<T> {
 		hexstring(&self.0, f, &LOWERHEX_ALPHABET)
 	}
 }
+
+//----------------------------------------------------------------
+
+#[test]
+fn test_hex_into() {
+	let bits = [0x0123_4567u32, 0x89ab_cdef];
+	let mut buf = [0u8; 20];
+	assert_eq!(bits.bit_to_hex_into(&mut buf), 16);
+	assert_eq!(&buf[..16], format!("{:x}", bits.bit_fmt()).as_bytes());
+	assert_eq!(buf[16..], [0; 4]);
+
+	let mut small = [0u8; 15];
+	assert_eq!(bits.bit_to_hex_into(&mut small), 0);
+	assert_eq!(small, [0; 15]);
+
+	let mut buf = [0u8; 2];
+	assert_eq!(0xa5u8.bit_to_hex_into(&mut buf), 2);
+	assert_eq!(&buf, format!("{:x}", 0xa5u8.bit_fmt()).as_bytes());
+}
+
+#[test]
+fn test_msb_first() {
+	let bits = [0x0000_0105u32, 0x8000_0000];
+	assert_eq!(format!("{}", bits.bit_fmt()), "10100000_10000000_00000000_00000000_00000000_00000000_00000000_00000001");
+	assert_eq!(format!("{}", bits.bit_fmt().msb_first()), "10000000_00000000_00000000_00000000_00000000_00000000_00000001_00000101");
+	assert_eq!(format!("{}", 0xa5u8.bit_fmt().msb_first()), format!("{:08b}", 0xa5u8));
+
+	let bools = [true, false, false, false, false, false, false, false, false, true, true];
+	assert_eq!(format!("{}", bools.bit_fmt()), "10000000_011");
+	assert_eq!(format!("{}", bools.bit_fmt().msb_first()), "110_00000001");
+	assert_eq!(format!("{}", [0u8; 0].bit_fmt().msb_first()), "");
+}
+
+#[test]
+fn test_truncated() {
+	let bits = [0x0123_4567u32, 0x89ab_cdef];
+	let full = format!("{:x}", bits.bit_fmt());
+	assert_eq!(format!("{:x}", bits.bit_fmt().truncated(16)), full);
+	assert_eq!(format!("{:x}", bits.bit_fmt().truncated(15)), format!("{}…", &full[..15]));
+	assert_eq!(format!("{:X}", bits.bit_fmt().truncated(3).ascii()), "E6A...");
+	assert_eq!(format!("{:x}", bits.bit_fmt().truncated(0)), "…");
+	assert_eq!(format!("{}", 0x0fu8.bit_fmt().truncated(8)), "11110000");
+	assert_eq!(format!("{}", 0x0fu16.bit_fmt().truncated(10)), "11110000_0…");
+
+	let large = [0x5555_5555u32; 10000];
+	let s = format!("{}", large.bit_fmt().truncated(20).ascii());
+	assert_eq!(s, "10101010_10101010_10...");
+	assert_eq!(format!("{}", [0u8; 0].bit_fmt().truncated(0)), "");
+}
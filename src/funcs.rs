@@ -0,0 +1,495 @@
+use super::{BitOp, BitSet};
+
+/// Bitwise OR of all the sets into `dst`.
+///
+/// Panics if the sets do not have the same length as `dst`.
+#[inline]
+pub fn bit_or_all<'a, T: 'a + ?Sized + BitSet, I: IntoIterator<Item = &'a T>>(dst: &mut T, sets: I) {
+	for set in sets {
+		dst.bit_or(set);
+	}
+}
+
+/// Bitwise AND of all the sets into `dst`.
+///
+/// Panics if the sets do not have the same length as `dst`.
+#[inline]
+pub fn bit_and_all<'a, T: 'a + ?Sized + BitSet, I: IntoIterator<Item = &'a T>>(dst: &mut T, sets: I) {
+	for set in sets {
+		dst.bit_and(set);
+	}
+}
+
+/// Returns the bitwise OR of all the sets.
+///
+/// Returns `T::default()` for no sets.
+/// The OR is associative so the optimizer already splits the fold into independent chains, an explicit tree reduction measured slower.
+/// Panics if the sets do not have the same length.
+///
+/// ```
+/// use bitset_core::bit_or_reduce;
+///
+/// assert_eq!(bit_or_reduce(&[0x01u8, 0x02, 0x10, 0x80, 0x01]), 0x93);
+/// assert_eq!(bit_or_reduce::<u32>(&[]), 0);
+/// ```
+#[inline]
+pub fn bit_or_reduce<T: Clone + Default + BitSet>(sets: &[T]) -> T {
+	match sets.split_first() {
+		Some((first, rest)) => {
+			let mut result = first.clone();
+			bit_or_all(&mut result, rest);
+			result
+		},
+		None => T::default(),
+	}
+}
+
+/// Returns if two bitsets of possibly different container types hold the same bits.
+///
+/// Bitsets of different lengths are never equal.
+#[inline]
+pub fn bit_eq_words<A: ?Sized + BitSet, B: ?Sized + BitSet>(a: &A, b: &B) -> bool {
+	a.bit_len() == b.bit_len() && (0..a.bit_len()).all(|i| a.bit_test(i) == b.bit_test(i))
+}
+
+/// Copies the bits of `src` into `dst` of a possibly different container type.
+///
+/// `bit X` of `src` becomes `bit X` of `dst` regardless of the width of the backing words.
+/// Panics if the bitsets do not have the same length.
+///
+/// ```
+/// use bitset_core::bit_transcode;
+///
+/// let bytes = [0x67u8, 0x45, 0x23, 0x01];
+/// let mut word = 0u32;
+/// bit_transcode(&mut word, &bytes[..]);
+/// assert_eq!(word, 0x0123_4567);
+/// ```
+#[inline]
+pub fn bit_transcode<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &mut B, src: &A) {
+	assert_eq!(dst.bit_len(), src.bit_len());
+	for i in 0..src.bit_len() {
+		dst.bit_cond(i, src.bit_test(i));
+	}
+}
+
+// Visits the bits of src as words of at most 64 bits along with the index of their first bit and their number of bits.
+// The visited words of bit_fold_words are either all as wide as the backing words or groups of 64 bits.
+#[inline]
+fn fold_offsets<B: ?Sized + BitSet, F: FnMut(usize, usize, u64)>(src: &B, mut f: F) {
+	let len = src.bit_len();
+	let words = src.bit_fold_words(0, |n, _, _| n + 1);
+	if words == 0 {
+		return;
+	}
+	let width = if (words - 1) * 64 < len { 64 } else { len / words };
+	// Words which do not tile the bits at the inferred width are read a bit at a time instead
+	if width == 0 || len > words * 64 || width < 64 && width * words != len {
+		for i in 0..len {
+			f(i, 1, src.bit_test(i) as u64);
+		}
+		return;
+	}
+	src.bit_fold_words((), |(), index, word| {
+		let start = index * width;
+		let n = core::cmp::min(width, len - start);
+		f(start, n, if n < 64 { word & !(!0 << n) } else { word })
+	});
+}
+
+/// Bitwise OR of `src` into `dst` of a possibly different container type.
+///
+/// Reads `src` a word at a time and only writes the bits of `dst` which change.
+/// Panics if the bitsets do not have the same length.
+///
+/// ```
+/// use bitset_core::bit_or_from;
+///
+/// let mut words = [0x0000_00ffu32, 0];
+/// bit_or_from(&mut words[..], &[0x00u8, 0xff, 0, 0, 0, 0, 0, 0x80][..]);
+/// assert_eq!(words, [0x0000_ffff, 0x8000_0000]);
+/// ```
+#[inline]
+pub fn bit_or_from<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &mut A, src: &B) {
+	assert_eq!(dst.bit_len(), src.bit_len());
+	fold_offsets(src, |start, _, mut word| {
+		while word != 0 {
+			dst.bit_set(start + word.trailing_zeros() as usize);
+			word &= word - 1;
+		}
+	});
+}
+
+/// Bitwise AND of `src` into `dst` of a possibly different container type.
+///
+/// Reads `src` a word at a time and only writes the bits of `dst` which change.
+/// Panics if the bitsets do not have the same length.
+#[inline]
+pub fn bit_and_from<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &mut A, src: &B) {
+	assert_eq!(dst.bit_len(), src.bit_len());
+	fold_offsets(src, |start, n, word| {
+		let mut zeros = if n < 64 { !word & !(!0 << n) } else { !word };
+		while zeros != 0 {
+			dst.bit_reset(start + zeros.trailing_zeros() as usize);
+			zeros &= zeros - 1;
+		}
+	});
+}
+
+/// Bitwise XOR of `src` into `dst` of a possibly different container type.
+///
+/// Reads `src` a word at a time and only writes the bits of `dst` which change.
+/// Panics if the bitsets do not have the same length.
+#[inline]
+pub fn bit_xor_from<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &mut A, src: &B) {
+	assert_eq!(dst.bit_len(), src.bit_len());
+	fold_offsets(src, |start, _, mut word| {
+		while word != 0 {
+			dst.bit_flip(start + word.trailing_zeros() as usize);
+			word &= word - 1;
+		}
+	});
+}
+
+/// Counts the number of set bits in the words, usable in const contexts.
+#[inline]
+pub const fn bit_count_slice_u64(words: &[u64]) -> usize {
+	let mut count = 0;
+	let mut i = 0;
+	while i < words.len() {
+		count += words[i].count_ones() as usize;
+		i += 1;
+	}
+	count
+}
+
+/// Counts the number of set bits in the words, usable in const contexts.
+#[inline]
+pub const fn bit_count_slice_u32(words: &[u32]) -> usize {
+	let mut count = 0;
+	let mut i = 0;
+	while i < words.len() {
+		count += words[i].count_ones() as usize;
+		i += 1;
+	}
+	count
+}
+
+/// Returns a new set with the bits set in either `a` or `b`.
+///
+/// Panics if the sets do not have the same length.
+#[inline]
+pub fn bit_union<T: Clone + BitSet>(a: &T, b: &T) -> T {
+	let mut result = a.clone();
+	result.bit_or(b);
+	result
+}
+
+/// Returns a new set with the bits set in both `a` and `b`.
+///
+/// Panics if the sets do not have the same length.
+#[inline]
+pub fn bit_intersection<T: Clone + BitSet>(a: &T, b: &T) -> T {
+	let mut result = a.clone();
+	result.bit_and(b);
+	result
+}
+
+/// Returns a new set with the bits set in `a` but not in `b`.
+///
+/// Panics if the sets do not have the same length.
+#[inline]
+pub fn bit_difference<T: Clone + BitSet>(a: &T, b: &T) -> T {
+	let mut result = a.clone();
+	result.bit_andnot(b);
+	result
+}
+
+/// Returns a new set with the bits set in exactly one of `a` and `b`.
+///
+/// Panics if the sets do not have the same length.
+#[inline]
+pub fn bit_symmetric_difference<T: Clone + BitSet>(a: &T, b: &T) -> T {
+	let mut result = a.clone();
+	result.bit_xor(b);
+	result
+}
+
+/// Writes the bits set in exactly one of `a` and `b` into `out` without cloning either.
+///
+/// The result marks the bits which changed between two versions of a set.
+/// Panics if the sets do not have the same length.
+///
+/// ```
+/// use bitset_core::bit_xor_into;
+///
+/// let mut changed = 0u16;
+/// bit_xor_into(&mut changed, &0x00f0, &0x0ff0);
+/// assert_eq!(changed, 0x0f00);
+/// ```
+#[inline]
+pub fn bit_xor_into<T: ?Sized + BitSet>(out: &mut T, a: &T, b: &T) {
+	assert_eq!(a.bit_len(), b.bit_len());
+	out.bit_combine_into(a, b, BitOp::Xor);
+}
+
+//----------------------------------------------------------------
+
+#[cfg(test)]
+fn random_masks() -> [[u32; 4]; 10] {
+	let mut random = super::xorshift(0x2545_f491);
+	let mut masks = [[0u32; 4]; 10];
+	for mask in masks.iter_mut() {
+		for word in mask.iter_mut() {
+			*word = random();
+		}
+	}
+	masks
+}
+
+#[test]
+fn test_fold_all() {
+	let masks = random_masks();
+
+	let mut or = [0u32; 4];
+	let mut and = [!0u32; 4];
+	for mask in &masks {
+		for i in 0..4 {
+			or[i] |= mask[i];
+			and[i] &= mask[i];
+		}
+	}
+
+	let mut dst = [0u32; 4];
+	bit_or_all(&mut dst[..], masks.iter().map(|mask| &mask[..]));
+	assert_eq!(dst, or);
+
+	let mut dst = [!0u32; 4];
+	bit_and_all(&mut dst[..], masks.iter().map(|mask| &mask[..]));
+	assert_eq!(dst, and);
+
+	let mut dst = 0u8;
+	bit_or_all(&mut dst, &[0x01, 0x02, 0x80]);
+	assert_eq!(dst, 0x83);
+}
+
+#[test]
+fn test_or_reduce() {
+	let masks = random_masks();
+	let mut words = [0u128; 10];
+	for (word, mask) in words.iter_mut().zip(&masks) {
+		bit_transcode(word, &mask[..]);
+	}
+	for len in 0..=words.len() {
+		let mut expected = 0u128;
+		bit_or_all(&mut expected, &words[..len]);
+		assert_eq!(bit_or_reduce(&words[..len]), expected);
+	}
+
+	let masks = [super::BitArray::<100, 2>::from_words([0x1, 0x2]); 64];
+	let mut bits = masks;
+	for (i, bits) in bits.iter_mut().enumerate() {
+		bits.bit_set(i + 36);
+	}
+	let reduced = bit_or_reduce(&bits);
+	assert_eq!(reduced.bit_count(), 1 + 64);
+	assert!(reduced.bit_test(0) && reduced.bit_test(65) && reduced.bit_test(99));
+	assert_eq!(bit_or_reduce(&masks[..1]), masks[0]);
+}
+
+#[test]
+fn test_eq_words() {
+	let bytes = bitset!([0u8; 16]; 0, 7, 8, 31, 64, 100, 127);
+	let words = bitset!([0u16; 8]; 0, 7, 8, 31, 64, 100, 127);
+	let dwords = bitset!([0u32; 4]; 0, 7, 8, 31, 64, 100, 127);
+	let qwords = bitset!([0u64; 2]; 0, 7, 8, 31, 64, 100, 127);
+	let oword = bitset!(0u128; 0, 7, 8, 31, 64, 100, 127);
+	let simd = bitset!([[0u32; 4]; 1]; 0, 7, 8, 31, 64, 100, 127);
+
+	assert!(bit_eq_words(&bytes[..], &words[..]));
+	assert!(bit_eq_words(&bytes[..], &dwords[..]));
+	assert!(bit_eq_words(&words[..], &qwords[..]));
+	assert!(bit_eq_words(&dwords[..], &oword));
+	assert!(bit_eq_words(&qwords[..], &simd[..]));
+	assert!(bit_eq_words(&oword, &simd[..]));
+
+	let other = bitset!([0u32; 4]; 0, 7, 8, 31, 64, 100, 126);
+	assert!(!bit_eq_words(&bytes[..], &other[..]));
+	assert!(bit_eq_words(&bytes[..8], &qwords[..1]));
+	assert!(!bit_eq_words(&bytes[..], &qwords[..1]));
+}
+
+#[test]
+fn test_transcode() {
+	let oword = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+	let mut bytes = [0u8; 16];
+	let mut words = [0u16; 8];
+	let mut dwords = [0u32; 4];
+	let mut qwords = [0u64; 2];
+	let mut simd = [[0u32; 4]; 1];
+	let mut bools = [false; 128];
+	bit_transcode(&mut bytes[..], &oword);
+	assert_eq!(bytes, oword.to_le_bytes());
+
+	// Through every width in turn, the layouts match test_transmute
+	let mut copy = 0u128;
+	bit_transcode(&mut words[..], &bytes[..]);
+	bit_transcode(&mut dwords[..], &words[..]);
+	bit_transcode(&mut qwords[..], &dwords[..]);
+	bit_transcode(&mut simd[..], &qwords[..]);
+	bit_transcode(&mut bools[..], &simd[..]);
+	bit_transcode(&mut copy, &bools[..]);
+	assert_eq!(copy, oword);
+	assert_eq!(words[0], 0x3210);
+	assert_eq!(dwords[3], 0x0123_4567);
+	assert_eq!(qwords, [0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef]);
+	assert_eq!(simd, [dwords]);
+	assert!(bit_eq_words(&bools[..], &bytes[..]));
+
+	let mut wide = [0u64; 2];
+	bit_transcode(&mut wide[..], &words[..]);
+	bit_transcode(&mut dwords[..], &wide[..]);
+	bit_transcode(&mut bytes[..], &simd[..]);
+	assert_eq!(bytes, oword.to_le_bytes());
+}
+
+#[test]
+#[should_panic]
+fn test_transcode_len() {
+	bit_transcode(&mut [0u8; 3][..], &0u32);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_set_operations() {
+	let masks = random_masks();
+	let a = masks[0].to_vec();
+	let b = masks[1].to_vec();
+
+	assert_eq!(bit_union(&a, &b), *a.clone().bit_or(&b));
+	assert_eq!(bit_intersection(&a, &b), *a.clone().bit_and(&b));
+	assert_eq!(bit_difference(&a, &b), *a.clone().bit_andnot(&b));
+	assert_eq!(bit_symmetric_difference(&a, &b), *a.clone().bit_xor(&b));
+	assert_eq!(bit_union(&0x0fu8, &0x3c), 0x3f);
+	assert_eq!(bit_symmetric_difference(&0x0fu8, &0x3c), 0x33);
+
+	let mut out = vec![0; 4];
+	bit_xor_into(&mut out, &a, &b);
+	assert_eq!(out, *a.clone().bit_xor(&b));
+	let mut out = [!0u32; 4];
+	bit_xor_into(&mut out[..], &masks[2][..], &masks[3][..]);
+	assert_eq!(out, *masks[2].clone().bit_xor(&masks[3]));
+}
+
+#[test]
+fn test_combine_from() {
+	fn expected<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &A, src: &B, op: fn(bool, bool) -> bool) -> [bool; 128] {
+		let mut bits = [false; 128];
+		for (i, bit) in bits[..dst.bit_len()].iter_mut().enumerate() {
+			*bit = op(dst.bit_test(i), src.bit_test(i));
+		}
+		bits
+	}
+	#[track_caller]
+	fn check<A: ?Sized + BitSet, B: ?Sized + BitSet>(dst: &mut A, src: &B) {
+		let len = dst.bit_len();
+		let or = expected(dst, src, |x, y| x | y);
+		bit_or_from(dst, src);
+		assert!(bit_eq_words(dst, &or[..len]));
+		let and = expected(dst, src, |x, y| x & y);
+		bit_and_from(dst, src);
+		assert!(bit_eq_words(dst, &and[..len]));
+		let xor = expected(dst, src, |x, y| x ^ y);
+		bit_xor_from(dst, src);
+		assert!(bit_eq_words(dst, &xor[..len]));
+	}
+	let masks = random_masks();
+	let mut bools = [false; 128];
+	bit_transcode(&mut bools[..], &masks[4][..]);
+	let mut array = super::BitArray::<100, 2>::new();
+	array.bit_set_range(3..70);
+
+	check(&mut { masks[0] }[..], &masks[1][..]);
+	check(&mut { masks[0] }[..], &0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128);
+	check(&mut 0x5555_5555_5555_5555_0f0f_0f0f_0f0f_0f0fu128, &bools[..]);
+	check(&mut bools[..], &[[0x8001u16; 8]][..]);
+	check(&mut [0xa5u8; 16][..], &[[0x1234_5678_9abc_def0u64; 2]][..]);
+	check(&mut [false; 100][..], &array);
+	check(&mut array, &[true; 100][..]);
+	check(&mut [0u8; 0][..], &[0u64; 0][..]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_combine_from_containers() {
+	let mut words = vec![0x0f0f_0f0fu32, 0x8000_0001];
+	let bytes: std::boxed::Box<[u8]> = std::boxed::Box::new([0xf0, 0x00, 0xff, 0x00, 0x01, 0x00, 0x00, 0x80]);
+
+	bit_or_from(&mut words, &bytes);
+	assert_eq!(words, [0x0fff_0fff, 0x8000_0001]);
+	bit_and_from(&mut words, &bytes);
+	assert_eq!(words, [0x00ff_00f0, 0x8000_0001]);
+	bit_xor_from(&mut words, &bytes);
+	assert_eq!(words, [0, 0]);
+}
+
+#[test]
+fn test_combine_from_untiled() {
+	// Visits its 60 bits as groups of 7 bits, which the inferred width of 60 / 9 does not match
+	struct Groups([bool; 60]);
+	impl BitSet for Groups {
+		fn bit_len(&self) -> usize { self.0.len() }
+		fn bit_init(&mut self, value: bool) -> &mut Self { self.0.bit_init(value); self }
+		fn bit_test(&self, bit: usize) -> bool { self.0[bit] }
+		fn bit_set(&mut self, bit: usize) -> &mut Self { self.0[bit] = true; self }
+		fn bit_reset(&mut self, bit: usize) -> &mut Self { self.0[bit] = false; self }
+		fn bit_flip(&mut self, bit: usize) -> &mut Self { self.0[bit] ^= true; self }
+		fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self { self.0[bit] = value; self }
+		fn bit_all(&self) -> bool { self.0.bit_all() }
+		fn bit_any(&self) -> bool { self.0.bit_any() }
+		fn bit_eq(&self, rhs: &Self) -> bool { self.0 == rhs.0 }
+		fn bit_disjoint(&self, rhs: &Self) -> bool { self.0.bit_disjoint(&rhs.0) }
+		fn bit_subset(&self, rhs: &Self) -> bool { self.0.bit_subset(&rhs.0) }
+		fn bit_or(&mut self, rhs: &Self) -> &mut Self { self.0.bit_or(&rhs.0); self }
+		fn bit_and(&mut self, rhs: &Self) -> &mut Self { self.0.bit_and(&rhs.0); self }
+		fn bit_andnot(&mut self, rhs: &Self) -> &mut Self { self.0.bit_andnot(&rhs.0); self }
+		fn bit_xor(&mut self, rhs: &Self) -> &mut Self { self.0.bit_xor(&rhs.0); self }
+		fn bit_not(&mut self) -> &mut Self { self.0.bit_not(); self }
+		fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self { self.0.bit_mask(&rhs.0, &mask.0); self }
+		fn bit_count(&self) -> usize { self.0.bit_count() }
+		fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, mut f: F) -> B {
+			let mut acc = init;
+			for (index, group) in self.0.chunks(7).enumerate() {
+				let word = group.iter().rev().fold(0, |word, &bit| word << 1 | bit as u64);
+				acc = f(acc, index, word);
+			}
+			acc
+		}
+	}
+	let mut src = Groups([false; 60]);
+	src.bit_set(0).bit_set(7).bit_set(20).bit_set(59);
+	let mut bools = [false; 60];
+	bit_or_from(&mut bools[..], &src);
+	assert_eq!(bools, src.0);
+	bit_xor_from(&mut bools[..], &src);
+	assert!(bools.bit_none());
+	bools = [true; 60];
+	bit_and_from(&mut bools[..], &src);
+	assert_eq!(bools, src.0);
+}
+
+#[test]
+#[should_panic]
+fn test_combine_from_len() {
+	bit_or_from(&mut [0u8; 3][..], &0u32);
+}
+
+#[test]
+fn test_const_count() {
+	const TABLE: [u64; 3] = [0xff, !0, 0x8000_0000_0000_0001];
+	const COUNT: usize = bit_count_slice_u64(&TABLE);
+	const COUNT32: usize = bit_count_slice_u32(&[0x0f0f, 1, 0]);
+	assert_eq!(COUNT, TABLE[..].bit_count());
+	assert_eq!(COUNT, 74);
+	assert_eq!(COUNT32, 9);
+}
@@ -0,0 +1,179 @@
+use core::{cmp, iter};
+use super::BitSet;
+
+/// Iterator over the bits packed into bytes.
+///
+/// Returned by `BitSet::bit_bytes`.
+pub struct BitBytes<'a, T: ?Sized> {
+	bits: &'a T,
+	index: usize,
+}
+
+impl<'a, T: ?Sized> BitBytes<'a, T> {
+	#[inline]
+	pub(crate) fn new(bits: &'a T) -> BitBytes<'a, T> {
+		BitBytes { bits, index: 0 }
+	}
+}
+
+impl<'a, T: ?Sized> Clone for BitBytes<'a, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		BitBytes { bits: self.bits, index: self.index }
+	}
+}
+
+impl<'a, T: ?Sized + BitSet> Iterator for BitBytes<'a, T> {
+	type Item = u8;
+
+	#[inline]
+	fn next(&mut self) -> Option<u8> {
+		let len = self.bits.bit_len();
+		if self.index >= len {
+			return None;
+		}
+		let mut byte = 0;
+		for i in 0..cmp::min(8, len - self.index) {
+			byte |= (self.bits.bit_test(self.index + i) as u8) << i;
+		}
+		self.index += 8;
+		Some(byte)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let bits = self.bits.bit_len().saturating_sub(self.index);
+		let len = bits / 8 + (bits & 7 != 0) as usize;
+		(len, Some(len))
+	}
+}
+
+impl<'a, T: ?Sized + BitSet> ExactSizeIterator for BitBytes<'a, T> {}
+impl<'a, T: ?Sized + BitSet> iter::FusedIterator for BitBytes<'a, T> {}
+
+/// Iterator over the bits which differ between two bitsets.
+///
+/// Returned by `BitSet::bit_changes`.
+pub struct BitChanges<'a, T: ?Sized> {
+	old: &'a T,
+	new: &'a T,
+	// Start of the next word to read
+	index: usize,
+	// Differing bits of the current word not yet yielded
+	word: u64,
+	base: usize,
+}
+
+impl<'a, T: ?Sized + BitSet> BitChanges<'a, T> {
+	#[inline]
+	pub(crate) fn new(old: &'a T, new: &'a T) -> BitChanges<'a, T> {
+		assert_eq!(old.bit_len(), new.bit_len());
+		BitChanges { old, new, index: 0, word: 0, base: 0 }
+	}
+
+	// Differing bits among the 64 bits starting at the given bit, zero-extended past the end
+	#[inline]
+	fn xor_word(&self, start: usize) -> u64 {
+		let mut word = 0;
+		for i in start..cmp::min(start + 64, self.old.bit_len()) {
+			word |= ((self.old.bit_test(i) != self.new.bit_test(i)) as u64) << (i - start);
+		}
+		word
+	}
+}
+
+impl<'a, T: ?Sized> Clone for BitChanges<'a, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		BitChanges { old: self.old, new: self.new, index: self.index, word: self.word, base: self.base }
+	}
+}
+
+impl<'a, T: ?Sized + BitSet> Iterator for BitChanges<'a, T> {
+	type Item = (usize, bool, bool);
+
+	#[inline]
+	fn next(&mut self) -> Option<(usize, bool, bool)> {
+		// Skip the words without changes
+		while self.word == 0 {
+			if self.index >= self.old.bit_len() {
+				return None;
+			}
+			self.base = self.index;
+			self.word = self.xor_word(self.index);
+			self.index += 64;
+		}
+		let i = self.base + self.word.trailing_zeros() as usize;
+		self.word &= self.word - 1;
+		let old = self.old.bit_test(i);
+		Some((i, old, !old))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let pending = self.word.count_ones() as usize;
+		(pending, Some(pending + self.old.bit_len().saturating_sub(self.index)))
+	}
+}
+
+impl<'a, T: ?Sized + BitSet> iter::FusedIterator for BitChanges<'a, T> {}
+
+//----------------------------------------------------------------
+
+#[test]
+fn test_bytes() {
+	let bytes = [0x01u8, 0x80, 0x5a, 0xff];
+	assert!(bytes.bit_bytes().eq(bytes.iter().copied()));
+	assert_eq!(bytes.bit_bytes().len(), 4);
+
+	let dwords = [0x0123_4567u32, 0x89ab_cdef];
+	let mut iter = dwords.bit_bytes();
+	assert!(iter.by_ref().take(4).eq(dwords[0].to_le_bytes().iter().copied()));
+	assert!(iter.eq(dwords[1].to_le_bytes().iter().copied()));
+
+	let simd = [[0x1234u16; 8]; 2];
+	assert!(simd.bit_bytes().eq([0x34u8, 0x12].iter().copied().cycle().take(32)));
+}
+
+#[test]
+fn test_changes() {
+	let old = [0x0123_4567u32, 0x89ab_cdef, 0];
+	let new = [0x0123_4577u32, 0x09ab_cdef, 1];
+	let mut expected = [(0, false, false); 3];
+	let mut n = 0;
+	for i in 0..old.bit_len() {
+		if old.bit_test(i) != new.bit_test(i) {
+			expected[n] = (i, old.bit_test(i), new.bit_test(i));
+			n += 1;
+		}
+	}
+	assert_eq!(n, 3);
+	assert!(old.bit_changes(&new).eq(expected.iter().copied()));
+	assert_eq!(expected, [(4, false, true), (63, true, false), (64, false, true)]);
+	assert_eq!(old.bit_changes(&old).next(), None);
+
+	let mut iter = [[0u8; 16]; 1].bit_changes(&[[0xff; 16]]);
+	assert_eq!(iter.by_ref().count(), 128);
+	assert_eq!(iter.next(), None);
+
+	// Changes in the last bits of a word, at the start of the next and past long unchanged stretches
+	let mut old = [false; 200];
+	let mut new = old;
+	for &i in &[0, 63, 64, 127, 199] {
+		new[i] = true;
+	}
+	old[130] = true;
+	let mut iter = old.bit_changes(&new);
+	assert_eq!(iter.size_hint(), (0, Some(200)));
+	assert_eq!(iter.next(), Some((0, false, true)));
+	assert_eq!(iter.size_hint(), (1, Some(137)));
+	let rest = [(63, false, true), (64, false, true), (127, false, true), (130, true, false), (199, false, true)];
+	assert!(iter.clone().eq(rest.iter().copied()));
+	assert_eq!(iter.count(), 5);
+}
+
+#[test]
+#[should_panic]
+fn test_changes_len() {
+	let _ = [0u8; 2].bit_changes(&[0; 3]);
+}
@@ -43,7 +43,7 @@ assert_eq!(bits.bit_test(1), false);
 assert_eq!(bits.bit_count(), 4 * 32 - 2);
 ```
 
-Simd optimization is provided by using underlying primitives such as `[u32; 4]` which match the hardware's 128-bit simd registers. The compiler is heavily encouraged to vectorize these primitives.
+Simd optimization is provided by using underlying primitives such as `[u32; 4]` which match the hardware's 128-bit simd registers. The compiler is heavily encouraged to vectorize these primitives. Wrap the container in `Aligned16`, `Aligned32` or `Aligned64` to guarantee its alignment.
 
 ```rust
 use bitset_core::BitSet;
@@ -59,8 +59,14 @@ a.bit_not();
 assert_eq!(a, [[0xffffffffu32; 4]; 16]);
 ```
 
+For a bitset with an exact number of bits that is not a multiple of the word size use `BitArray<BITS, WORDS>`, which keeps the unused high bits cleared.
+
 For non fixed-size containers using the `std` feature `BitSet` is also implemented for `Vec<T>` and `Box<[T]>` (where `[T]`: `BitSet`).
 
+For interop with APIs using booleans `BitSet` is implemented for `[bool]`, storing one bit per element.
+
+On targets without a popcount instruction enable the `lut-popcount` feature to count the bits of `[u8]` and `[u16]` slices with a 256 byte lookup table instead of the `count_ones` fallback loop.
+
 Future work includes making everything const fn to enable all of this at compiletime, blocked on support for traits in const fn.
 
 License
@@ -76,25 +82,128 @@ for inclusion in the work by you, shall be licensed as above, without any additi
 
 #![no_std]
 
+use core::ops;
+
 #[cfg(any(test, feature = "std"))]
 #[macro_use]
 extern crate std;
 
 /// The BitSet API.
+///
+/// The single bit methods do not check the bit index against `bit_len()` to stay fast.
+/// Out of range indices panic or touch unspecified bits depending on the container, see `bit_get` and friends for checked access.
+/// The integer containers catch out of range indices with a debug assertion.
+///
+/// The word level methods operate on the unsigned integers backing the container.
+/// For the simd-like containers these are the individual lanes in memory order.
+///
+/// This trait is not object safe, see `DynBitSet` for the subset of methods available on trait objects.
+///
+/// Shared references do not implement `BitSet` as most of the required methods mutate the bits.
+/// Generic read-only code should instead take `&T` where `T: ?Sized + BitSet`, which accepts slices and nested references alike:
+///
+/// ```
+/// use bitset_core::BitSet;
+///
+/// fn density<T: ?Sized + BitSet>(set: &T) -> f64 {
+///     set.bit_count() as f64 / set.bit_len() as f64
+/// }
+///
+/// let array = [0xffu8, 0x00];
+/// let nested = &&array[..];
+/// assert_eq!(density(&array[..]), 0.5);
+/// assert_eq!(density(*nested), 0.5);
+/// assert_eq!(density(&0xf0u8), 0.5);
+/// ```
 pub trait BitSet {
 	/// Returns total number of bits.
 	fn bit_len(&self) -> usize;
 
 	/// Initializes all bits.
 	fn bit_init(&mut self, value: bool) -> &mut Self;
+	/// Resets all bits, same as `bit_init(false)`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	/// let mut bits = [0x5au8; 4];
+	/// bits.bit_clear_all();
+	/// assert!(bits.bit_none());
+	/// ```
+	#[inline]
+	fn bit_clear_all(&mut self) -> &mut Self {
+		self.bit_init(false)
+	}
+	/// Sets all bits, same as `bit_init(true)`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	/// let mut bits = [0x5au8; 4];
+	/// bits.bit_set_all();
+	/// assert!(bits.bit_all());
+	/// ```
+	#[inline]
+	fn bit_set_all(&mut self) -> &mut Self {
+		self.bit_init(true)
+	}
 	/// Format the bits.
 	#[inline]
 	fn bit_fmt(&self) -> &BitFmt<Self> {
 		unsafe { &*(self as *const _ as *const _) }
 	}
+	/// Writes the bits as lowercase hex into `out`, returns the number of bytes written.
+	///
+	/// The output matches the `LowerHex` formatting of `bit_fmt` and takes two bytes for every started group of 8 bits.
+	/// Returns 0 without writing anything if `out` is too small.
+	#[inline]
+	fn bit_to_hex_into(&self, out: &mut [u8]) -> usize {
+		fmt::hex_into(self, out)
+	}
+	/// Returns an iterator over the bits packed into bytes.
+	///
+	/// Bit 0 of the first byte is bit 0 of the bitset regardless of the backing words, a final partial byte is padded with zeros.
+	#[inline]
+	fn bit_bytes(&self) -> BitBytes<'_, Self> {
+		BitBytes::new(self)
+	}
+	/// Returns an iterator over the bits which differ between self and rhs.
+	///
+	/// Yields `(index, old, new)` with the value of the bit in self and in rhs, which are always complements.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let old = 0b0110u8;
+	/// let new = 0b1100u8;
+	/// assert!(old.bit_changes(&new).eq([(1, true, false), (3, false, true)].iter().copied()));
+	/// ```
+	#[inline]
+	fn bit_changes<'a>(&'a self, rhs: &'a Self) -> BitChanges<'a, Self> {
+		BitChanges::new(self, rhs)
+	}
+	/// Starts building a bitset from self with chainable methods.
+	#[inline]
+	fn bit_builder(self) -> BitBuilder<Self> where Self: Sized {
+		BitBuilder::new(self)
+	}
 
 	/// Returns if the given bit is set.
 	fn bit_test(&self, bit: usize) -> bool;
+	/// Returns if the given bit is set.
+	///
+	/// Alias of `bit_test` named after `HashSet::contains`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0b1010u8];
+	/// assert!(bits.bit_contains(3));
+	/// assert!(!bits.bit_contains(2));
+	/// ```
+	#[inline]
+	fn bit_contains(&self, bit: usize) -> bool {
+		self.bit_test(bit)
+	}
 	/// Sets the given bit.
 	fn bit_set(&mut self, bit: usize) -> &mut Self;
 	/// Resets the given bit.
@@ -103,21 +212,211 @@ pub trait BitSet {
 	fn bit_flip(&mut self, bit: usize) -> &mut Self;
 	/// Conditionally sets or resets the given bit.
 	fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self;
+	/// Sets the given bit and returns its previous value.
+	#[inline]
+	fn bit_test_and_set(&mut self, bit: usize) -> bool {
+		let old = self.bit_test(bit);
+		self.bit_set(bit);
+		old
+	}
+	/// Sets the given bit and returns if it was previously cleared.
+	///
+	/// This is the negation of `bit_test_and_set`, named for guards which run only on first touch.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut initialized = 0u32;
+	/// assert!(initialized.bit_set_once(5));
+	/// assert!(!initialized.bit_set_once(5));
+	/// ```
+	#[inline]
+	fn bit_set_once(&mut self, bit: usize) -> bool {
+		!self.bit_test_and_set(bit)
+	}
+	/// Resets the given bit and returns its previous value.
+	#[inline]
+	fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+		let old = self.bit_test(bit);
+		self.bit_reset(bit);
+		old
+	}
+	/// Flips the given bit and returns its previous value.
+	#[inline]
+	fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+		let old = self.bit_test(bit);
+		self.bit_flip(bit);
+		old
+	}
+	/// Conditionally sets or resets all the bits in the range.
+	#[inline]
+	fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+		assert!(range.start <= range.end && range.end <= self.bit_len());
+		for i in range {
+			self.bit_cond(i, value);
+		}
+		self
+	}
+	/// Sets all the bits in the range, same as `bit_cond_range(range, true)`.
+	#[inline]
+	fn bit_set_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+		self.bit_cond_range(range, true)
+	}
+	/// Sets all the bits at the given indices.
+	///
+	/// Consecutive indices falling in the same word are combined into a single write, sorted indices are the fastest.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut bits = [0u8; 2];
+	/// bits.bit_set_bits(&[1, 3, 4, 12]);
+	/// assert_eq!(bits, [0x1a, 0x10]);
+	/// ```
+	#[inline]
+	fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+		for &bit in indices {
+			self.bit_set(bit);
+		}
+		self
+	}
+
+	/// Returns if the given bit is set, out of range bits are never set.
+	#[inline]
+	fn bit_get(&self, bit: usize) -> bool {
+		bit < self.bit_len() && self.bit_test(bit)
+	}
+	/// Sets the given bit, does nothing if the bit is out of range.
+	#[inline]
+	fn bit_set_checked(&mut self, bit: usize) -> &mut Self {
+		if bit < self.bit_len() {
+			self.bit_set(bit);
+		}
+		self
+	}
+	/// Resets the given bit, does nothing if the bit is out of range.
+	#[inline]
+	fn bit_reset_checked(&mut self, bit: usize) -> &mut Self {
+		if bit < self.bit_len() {
+			self.bit_reset(bit);
+		}
+		self
+	}
+	/// Returns if the given bit of the given backing word is set.
+	///
+	/// Same as `bit_test(word * W + bit_in_word)` where `W` is the width of the backing words, without dividing the index back apart.
+	/// The words are the integers for the integer slices, the lanes for the simd containers and the `u64` words of `BitArray`.
+	/// Every element of `[bool]` is a word holding a single bit, other containers default to 64 bit words.
+	///
+	/// `bit_in_word` must be less than `W`, which is only checked in debug builds.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0u16, 0x0100];
+	/// assert!(bits.bit_test_at(1, 8));
+	/// assert_eq!(bits.bit_test_at(1, 8), bits.bit_test(24));
+	/// ```
+	#[inline]
+	fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+		debug_assert!(bit_in_word < 64, "bit index out of range");
+		self.bit_test(word * 64 + bit_in_word as usize)
+	}
+	/// Sets the given bit of the given backing word, see `bit_test_at`.
+	#[inline]
+	fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+		debug_assert!(bit_in_word < 64, "bit index out of range");
+		self.bit_set(word * 64 + bit_in_word as usize)
+	}
 
 	/// Returns if all bits are set.
+	///
+	/// Scans every word without branching so the loop vectorizes, see `bit_is_full` for an early exit.
+	///
+	/// Like `Iterator::all` this is vacuously true for a bitset without any bits,
+	/// which is then both all set and all cleared: `bit_all() == !bit_any()` only holds when `bit_len() > 0`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let empty: &[u32] = &[];
+	/// assert!(empty.bit_all());
+	/// assert!(!empty.bit_any());
+	/// assert!(empty.bit_none());
+	/// assert_eq!(empty.bit_count(), 0);
+	/// ```
 	fn bit_all(&self) -> bool;
 	/// Returns if any bits are set.
+	///
+	/// Scans every word without branching so the loop vectorizes, see `bit_is_empty` for an early exit.
+	/// Always false for a bitset without any bits.
 	fn bit_any(&self) -> bool;
 	/// Returns if none of the bits are set.
+	///
+	/// Always true for a bitset without any bits.
 	#[inline]
 	fn bit_none(&self) -> bool {
 		!self.bit_any()
 	}
+	/// Returns if none of the bits are set, stopping at the first word with any bits set.
+	///
+	/// Same result as `bit_none`, named after `HashSet::is_empty`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// assert!([0u32; 4].bit_is_empty());
+	/// assert!(![0, 0, 1, 0u32].bit_is_empty());
+	/// ```
+	#[inline]
+	fn bit_is_empty(&self) -> bool {
+		self.bit_first_nonzero_word().is_none()
+	}
+	/// Returns if all bits are set, stopping at the first word with any bits reset.
+	///
+	/// Same result as `bit_all`.
+	#[inline]
+	fn bit_is_full(&self) -> bool {
+		self.bit_find_first_zero().is_none()
+	}
+	/// Returns if all of the first `len` bits are set.
+	///
+	/// Useful for buffers larger than the logical number of bits. Panics if `len > bit_len()`.
+	#[inline]
+	fn bit_all_len(&self, len: usize) -> bool {
+		assert!(len <= self.bit_len());
+		(0..len).all(|i| self.bit_test(i))
+	}
+	/// Returns if any of the first `len` bits are set.
+	///
+	/// Useful for buffers larger than the logical number of bits. Panics if `len > bit_len()`.
+	#[inline]
+	fn bit_any_len(&self, len: usize) -> bool {
+		assert!(len <= self.bit_len());
+		(0..len).any(|i| self.bit_test(i))
+	}
 
 	/// Returns if the two bitsets are equal.
+	///
+	/// For the containers in this crate this agrees with `==` on the containers themselves.
+	/// Use `bit_eq_words` to compare the bits of different container types.
 	fn bit_eq(&self, rhs: &Self) -> bool;
 	/// Returns if the two bitsets have no bits in common.
 	fn bit_disjoint(&self, rhs: &Self) -> bool;
+	/// Returns if self and rhs share no set bits within the range.
+	///
+	/// Panics if the lengths differ or the range is out of bounds.
+	#[inline]
+	fn bit_disjoint_range(&self, rhs: &Self, range: ops::Range<usize>) -> bool {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		assert!(range.start <= range.end && range.end <= self.bit_len());
+		for i in range {
+			if self.bit_test(i) && rhs.bit_test(i) {
+				return false;
+			}
+		}
+		true
+	}
 	/// Returns if self is a subset of rhs.
 	fn bit_subset(&self, rhs: &Self) -> bool;
 	/// Returns if self is a superset of rhs.
@@ -125,6 +424,44 @@ pub trait BitSet {
 	fn bit_superset(&self, rhs: &Self) -> bool {
 		rhs.bit_subset(self)
 	}
+	/// Returns if the two bitsets are equal, only comparing the bits set in MASK.
+	#[inline]
+	fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+		let len = self.bit_len();
+		assert_eq!(len, rhs.bit_len());
+		assert_eq!(len, mask.bit_len());
+		(0..len).all(|i| !mask.bit_test(i) || self.bit_test(i) == rhs.bit_test(i))
+	}
+	/// Returns if self is a subset of rhs, only comparing the bits set in MASK.
+	#[inline]
+	fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+		let len = self.bit_len();
+		assert_eq!(len, rhs.bit_len());
+		assert_eq!(len, mask.bit_len());
+		(0..len).all(|i| !mask.bit_test(i) || !self.bit_test(i) || rhs.bit_test(i))
+	}
+	/// Writes the bits set in self but not in available into `out`, ie. `out = self & !available`.
+	///
+	/// `out` is empty exactly when self is a subset of available, listing its bits reports why it is not.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let required = 0b1011u8;
+	/// let mut missing = 0;
+	/// required.bit_missing_from(&0b0011, &mut missing);
+	/// assert_eq!(missing, 0b1000);
+	/// ```
+	#[inline]
+	fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+		let len = self.bit_len();
+		assert_eq!(len, available.bit_len());
+		assert_eq!(len, out.bit_len());
+		for i in 0..len {
+			out.bit_cond(i, self.bit_test(i) && !available.bit_test(i));
+		}
+	}
 
 	/// Bitwise OR.
 	fn bit_or(&mut self, rhs: &Self) -> &mut Self;
@@ -132,34 +469,899 @@ pub trait BitSet {
 	fn bit_and(&mut self, rhs: &Self) -> &mut Self;
 	/// Bitwise AND after NOT of rhs.
 	fn bit_andnot(&mut self, rhs: &Self) -> &mut Self;
+	/// Bitwise AND after NOT of rhs within the range, bits outside the range are left untouched.
+	///
+	/// Panics if the lengths differ or the range is out of bounds.
+	#[inline]
+	fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		assert!(range.start <= range.end && range.end <= self.bit_len());
+		for i in range {
+			if rhs.bit_test(i) {
+				self.bit_reset(i);
+			}
+		}
+		self
+	}
+	/// Bitwise AND after NOT of rhs, returns if any bits were cleared.
+	///
+	/// Useful to detect convergence when repeatedly subtracting from a set.
+	/// Panics if the lengths differ.
+	#[inline]
+	fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		let mut changed = false;
+		for i in 0..self.bit_len() {
+			if self.bit_test(i) && rhs.bit_test(i) {
+				self.bit_reset(i);
+				changed = true;
+			}
+		}
+		changed
+	}
+	/// Bitwise OR, returns the number of bits which went from cleared to set.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut bits = 0b0011u8;
+	/// assert_eq!(bits.bit_or_delta(&0b0110), 1);
+	/// assert_eq!(bits, 0b0111);
+	/// ```
+	#[inline]
+	fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		let mut delta = 0;
+		for i in 0..self.bit_len() {
+			if rhs.bit_test(i) && !self.bit_test_and_set(i) {
+				delta += 1;
+			}
+		}
+		delta
+	}
+	/// Bitwise AND after NOT of rhs, returns the number of bits which were cleared.
+	#[inline]
+	fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		let mut delta = 0;
+		for i in 0..self.bit_len() {
+			if rhs.bit_test(i) && self.bit_test_and_reset(i) {
+				delta += 1;
+			}
+		}
+		delta
+	}
 	/// Bitwise XOR.
 	fn bit_xor(&mut self, rhs: &Self) -> &mut Self;
 	/// Bitwise NOT.
 	fn bit_not(&mut self) -> &mut Self;
+	/// Bitwise NOT if `condition` is true, without branching on it.
+	#[inline]
+	fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+		self.bit_xor_broadcast(0u64.wrapping_sub(condition as u64))
+	}
 	/// Bitwise combine with MASK.
+	///
+	/// Computes `(self & !mask) | (rhs & mask)`, taking the bits of rhs where the mask is set.
 	fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self;
+	/// Bitwise combine with inverted MASK.
+	///
+	/// Computes `(self & mask) | (rhs & !mask)`, keeping the bits of self where the mask is set.
+	/// Panics if the lengths differ.
+	#[inline]
+	fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		let len = self.bit_len();
+		assert_eq!(len, rhs.bit_len());
+		assert_eq!(len, mask.bit_len());
+		for i in 0..len {
+			if !mask.bit_test(i) {
+				self.bit_cond(i, rhs.bit_test(i));
+			}
+		}
+		self
+	}
+	/// Bitwise OR with the AND of rhs and cond, ie. `self |= rhs & cond` in a single pass.
+	///
+	/// Panics if the lengths differ.
+	#[inline]
+	fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+		let len = self.bit_len();
+		assert_eq!(len, rhs.bit_len());
+		assert_eq!(len, cond.bit_len());
+		for i in 0..len {
+			if rhs.bit_test(i) && cond.bit_test(i) {
+				self.bit_set(i);
+			}
+		}
+		self
+	}
+	/// Bitwise combine of `a` with `b` through MASK, stored in self.
+	///
+	/// Computes `(a & !mask) | (b & mask)` without modifying the operands.
+	#[inline]
+	fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+		let len = self.bit_len();
+		assert_eq!(len, a.bit_len());
+		assert_eq!(len, b.bit_len());
+		assert_eq!(len, mask.bit_len());
+		for i in 0..len {
+			let value = if mask.bit_test(i) { b.bit_test(i) } else { a.bit_test(i) };
+			self.bit_cond(i, value);
+		}
+		self
+	}
+	/// Sets the bits which are set in at least `threshold` of the inputs, stored in self.
+	///
+	/// Majority voting over `n` inputs uses a threshold of `n / 2 + 1`.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut out = 0u8;
+	/// out.bit_vote_into(&[&0b0011, &0b0110, &0b1100], 2);
+	/// assert_eq!(out, 0b0110);
+	/// ```
+	#[inline]
+	fn bit_vote_into(&mut self, inputs: &[&Self], threshold: usize) -> &mut Self {
+		let len = self.bit_len();
+		for input in inputs {
+			assert_eq!(len, input.bit_len());
+		}
+		for i in 0..len {
+			let votes = inputs.iter().filter(|input| input.bit_test(i)).count();
+			self.bit_cond(i, votes >= threshold);
+		}
+		self
+	}
+	/// Bitwise OR of every backing word with the given word.
+	///
+	/// The word is truncated or zero-extended to the width of the backing words.
+	#[inline]
+	fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+		for i in 0..self.bit_len() {
+			if word >> (i % 64) & 1 != 0 {
+				self.bit_set(i);
+			}
+		}
+		self
+	}
+	/// Bitwise AND of every backing word with the given word.
+	///
+	/// The word is truncated or zero-extended to the width of the backing words.
+	#[inline]
+	fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+		for i in 0..self.bit_len() {
+			if word >> (i % 64) & 1 == 0 {
+				self.bit_reset(i);
+			}
+		}
+		self
+	}
+	/// Bitwise XOR of every backing word with the given word.
+	///
+	/// The word is truncated or zero-extended to the width of the backing words.
+	#[inline]
+	fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+		for i in 0..self.bit_len() {
+			if word >> (i % 64) & 1 != 0 {
+				self.bit_flip(i);
+			}
+		}
+		self
+	}
+	/// Rotates the bits within the range towards the higher indices.
+	///
+	/// The range is treated as a ring of `range.len()` bits, bits outside the range are left untouched.
+	#[inline]
+	fn bit_rotate_left_range(&mut self, range: ops::Range<usize>, n: usize) -> &mut Self {
+		assert!(range.start <= range.end && range.end <= self.bit_len());
+		let len = range.end - range.start;
+		let n = if len > 0 { n % len } else { 0 };
+		if n > 0 {
+			self.bit_reverse_range(range.start..range.end);
+			self.bit_reverse_range(range.start..range.start + n);
+			self.bit_reverse_range(range.start + n..range.end);
+		}
+		self
+	}
+	/// Rotates all the bits by `n` towards the higher indices, the highest bits wrap around to the lowest.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// assert_eq!(*0x8000_0001u32.bit_rotate_left(4), 0x18);
+	/// assert_eq!(*0x8000_0001u32.bit_rotate_right(4), 0x1800_0000);
+	/// ```
+	#[inline]
+	fn bit_rotate_left(&mut self, n: usize) -> &mut Self {
+		let len = self.bit_len();
+		self.bit_rotate_left_range(0..len, n)
+	}
+	/// Rotates all the bits by `n` towards the lower indices, the lowest bits wrap around to the highest.
+	#[inline]
+	fn bit_rotate_right(&mut self, n: usize) -> &mut Self {
+		let len = self.bit_len();
+		let n = if len > 0 { len - n % len } else { 0 };
+		self.bit_rotate_left_range(0..len, n)
+	}
+	/// Inserts a bit at the index, shifting the bits from the index onwards towards the higher indices.
+	///
+	/// The highest bit is shifted out and discarded, see `GrowableBitSet::bit_insert_grow` to keep it.
+	/// Panics if `index >= bit_len()`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut bits = 0b1011u8;
+	/// bits.bit_insert(1, false);
+	/// assert_eq!(bits, 0b10101);
+	/// assert!(!bits.bit_remove(1));
+	/// assert_eq!(bits, 0b1011);
+	/// ```
+	#[inline]
+	fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+		let len = self.bit_len();
+		assert!(index < len);
+		for i in (index + 1..len).rev() {
+			let bit = self.bit_test(i - 1);
+			self.bit_cond(i, bit);
+		}
+		self.bit_cond(index, value)
+	}
+	/// Removes the bit at the index and returns it, shifting the bits after the index towards the lower indices.
+	///
+	/// The highest bit is cleared.
+	/// Panics if `index >= bit_len()`.
+	#[inline]
+	fn bit_remove(&mut self, index: usize) -> bool {
+		let len = self.bit_len();
+		assert!(index < len);
+		let removed = self.bit_test(index);
+		for i in index + 1..len {
+			let bit = self.bit_test(i);
+			self.bit_cond(i - 1, bit);
+		}
+		self.bit_reset(len - 1);
+		removed
+	}
+	/// Shifts the first `len` bits by `n` towards the higher indices.
+	///
+	/// Bits shifted to `len` or beyond are discarded and the low `n` bits are cleared, bits from `len` onwards are left untouched.
+	/// Panics if `len > bit_len()`.
+	#[inline]
+	fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+		assert!(len <= self.bit_len());
+		for i in (0..len).rev() {
+			let value = i >= n && self.bit_test(i - n);
+			self.bit_cond(i, value);
+		}
+		self
+	}
+	/// Shifts all the bits by `n` towards the higher indices, filling the vacated low `n` bits with `fill`.
+	///
+	/// Bits shifted beyond `bit_len()` are discarded.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// assert_eq!(*0b1001u8.bit_shl_fill(2, true), 0b100111);
+	/// assert_eq!(*0x81u8.bit_shr_fill(4, true), 0xf8);
+	/// assert_eq!(*0x81u8.bit_shr_fill(4, false), 0x08);
+	/// ```
+	#[inline]
+	fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+		let len = self.bit_len();
+		self.bit_shl_len(n, len);
+		self.bit_cond_range(0..core::cmp::min(n, len), fill)
+	}
+	/// Shifts all the bits by `n` towards the lower indices, filling the vacated high `n` bits with `fill`.
+	///
+	/// Bits shifted below index zero are discarded.
+	#[inline]
+	fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+		let len = self.bit_len();
+		let n = core::cmp::min(n, len);
+		for i in 0..len - n {
+			let value = self.bit_test(i + n);
+			self.bit_cond(i, value);
+		}
+		self.bit_cond_range(len - n..len, fill)
+	}
+	/// Reverses the order of the bits within the range.
+	///
+	/// Bit `range.start + k` is swapped with bit `range.end - 1 - k`, bits outside the range are left untouched.
+	#[inline]
+	fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+		assert!(range.start <= range.end && range.end <= self.bit_len());
+		let (mut start, mut end) = (range.start, range.end);
+		while start + 1 < end {
+			end -= 1;
+			let lo = self.bit_test(start);
+			let hi = self.bit_test(end);
+			self.bit_cond(start, hi);
+			self.bit_cond(end, lo);
+			start += 1;
+		}
+		self
+	}
+	/// Reverses the order of the bits within every group of `group_bits` bits, keeping the order of the groups.
+	///
+	/// Panics if `group_bits` is zero or does not divide `bit_len()`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let mut bits = 0x0c_31u16;
+	/// bits.bit_reverse_groups(8);
+	/// assert_eq!(bits, 0x30_8c);
+	/// ```
+	#[inline]
+	fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+		let len = self.bit_len();
+		assert!(group_bits > 0);
+		assert_eq!(len % group_bits, 0);
+		for start in (0..len).step_by(group_bits) {
+			self.bit_reverse_range(start..start + group_bits);
+		}
+		self
+	}
+	/// Reverses the order of the bits within every nibble, same as `bit_reverse_groups(4)`.
+	#[inline]
+	fn bit_reverse_nibbles(&mut self) -> &mut Self {
+		self.bit_reverse_groups(4)
+	}
+	/// Swaps the `len` bits starting at `a` with the `len` bits starting at `b`.
+	///
+	/// Panics if the spans overlap or are out of range.
+	#[inline]
+	fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+		assert!(a + len <= self.bit_len() && b + len <= self.bit_len());
+		assert!(len == 0 || a + len <= b || b + len <= a);
+		for i in 0..len {
+			let x = self.bit_test(a + i);
+			let y = self.bit_test(b + i);
+			self.bit_cond(a + i, y);
+			self.bit_cond(b + i, x);
+		}
+		self
+	}
+	/// Combines two bitsets of possibly different lengths with the given operation.
+	///
+	/// The shorter operand is treated as if padded with zero bits, see `BitOp` for how this affects the tail.
+	/// Panics if `self.bit_len()` is not the length of the longer operand.
+	#[inline]
+	fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+		let len = core::cmp::max(a.bit_len(), b.bit_len());
+		assert_eq!(self.bit_len(), len);
+		for i in 0..len {
+			let x = i < a.bit_len() && a.bit_test(i);
+			let y = i < b.bit_len() && b.bit_test(i);
+			let value = match op {
+				BitOp::Or => x | y,
+				BitOp::And => x & y,
+				BitOp::AndNot => x & !y,
+				BitOp::Xor => x ^ y,
+			};
+			self.bit_cond(i, value);
+		}
+		self
+	}
+	/// Bitwise AND with the pattern repeated to cover the length of self.
+	///
+	/// Panics if the pattern is empty.
+	#[inline]
+	fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+		tiled(self, pattern, BitOp::And);
+		self
+	}
+	/// Bitwise OR with the pattern repeated to cover the length of self.
+	///
+	/// Panics if the pattern is empty.
+	#[inline]
+	fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+		tiled(self, pattern, BitOp::Or);
+		self
+	}
+	/// Bitwise XOR with the pattern repeated to cover the length of self.
+	///
+	/// Panics if the pattern is empty.
+	#[inline]
+	fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+		tiled(self, pattern, BitOp::Xor);
+		self
+	}
+	/// Bitwise OR of every backing word with the single backing word of rhs.
+	///
+	/// Panics if rhs is not exactly one backing word.
+	#[inline]
+	fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.bit_fold_words(0, |n, _, _| n + 1), 1);
+		self.bit_or_tiled(rhs)
+	}
+	/// Bitwise AND of every backing word with the single backing word of rhs.
+	///
+	/// Panics if rhs is not exactly one backing word.
+	#[inline]
+	fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.bit_fold_words(0, |n, _, _| n + 1), 1);
+		self.bit_and_tiled(rhs)
+	}
+	/// Bitwise XOR of every backing word with the single backing word of rhs.
+	///
+	/// Panics if rhs is not exactly one backing word.
+	#[inline]
+	fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+		assert_eq!(rhs.bit_fold_words(0, |n, _, _| n + 1), 1);
+		self.bit_xor_tiled(rhs)
+	}
 
 	/// Counts the number of set bits.
 	fn bit_count(&self) -> usize;
+	/// Counts the number of set bits.
+	///
+	/// Alias of `bit_count`, the set-like counterpart of `HashSet::len` which would be confused with `bit_len`.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0xf0u8, 0x01];
+	/// assert_eq!(bits.bit_population(), 5);
+	/// assert_eq!(bits.bit_population(), bits.bit_count());
+	/// ```
+	#[inline]
+	fn bit_population(&self) -> usize {
+		self.bit_count()
+	}
+
+	/// Counts the number of set bits up to `limit`, returning `limit` if there are at least as many.
+	///
+	/// Stops scanning as soon as the limit is reached, which is cheap for large sets when only small counts matter.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0x0fu8, 0xff, 0x01];
+	/// assert_eq!(bits.bit_count_up_to(3), 3);
+	/// assert_eq!(bits.bit_count_up_to(100), 13);
+	/// ```
+	#[inline]
+	fn bit_count_up_to(&self, limit: usize) -> usize {
+		let mut count = 0;
+		for i in 0..self.bit_len() {
+			if count >= limit {
+				break;
+			}
+			count += self.bit_test(i) as usize;
+		}
+		core::cmp::min(count, limit)
+	}
+	/// Counts the bits set in self but not in rhs without modifying either.
+	///
+	/// Panics if the lengths differ.
+	#[inline]
+	fn bit_andnot_count(&self, rhs: &Self) -> usize {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		(0..self.bit_len()).filter(|&i| self.bit_test(i) && !rhs.bit_test(i)).count()
+	}
+	/// Returns if at least `threshold` bits are set in both self and rhs.
+	///
+	/// Stops counting the intersection as soon as the threshold is reached.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let query = [0xffu8, 0x0f];
+	/// assert!(query.bit_and_count_at_least(&[0x0f, 0x01], 5));
+	/// assert!(!query.bit_and_count_at_least(&[0x0f, 0x01], 6));
+	/// ```
+	#[inline]
+	fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		let mut count = 0;
+		for i in 0..self.bit_len() {
+			if count >= threshold {
+				break;
+			}
+			count += (self.bit_test(i) && rhs.bit_test(i)) as usize;
+		}
+		count >= threshold
+	}
+	/// Returns if at most `max` bits differ between self and rhs.
+	///
+	/// Stops counting the Hamming distance as soon as it exceeds `max`, rejecting distant candidates without scanning all the bits.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let hash = [0xf0u8, 0x0f];
+	/// assert!(hash.bit_hamming_at_most(&[0xf1, 0x0e], 2));
+	/// assert!(!hash.bit_hamming_at_most(&[0xf1, 0x0e], 1));
+	/// ```
+	#[inline]
+	fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+		assert_eq!(self.bit_len(), rhs.bit_len());
+		let mut distance = 0;
+		for i in 0..self.bit_len() {
+			distance += (self.bit_test(i) != rhs.bit_test(i)) as usize;
+			if distance > max {
+				return false;
+			}
+		}
+		true
+	}
+	/// Jaccard similarity, the number of bits set in both divided by the number of bits set in either.
+	///
+	/// Returns `0.0` if neither has any bits set.
+	/// Panics if the lengths differ.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// assert_eq!(0b1110u8.bit_jaccard(&0b0111), 0.5);
+	/// assert_eq!(0u8.bit_jaccard(&0), 0.0);
+	/// ```
+	#[inline]
+	fn bit_jaccard(&self, rhs: &Self) -> f64 {
+		let count = self.bit_count();
+		let both = count - self.bit_andnot_count(rhs);
+		let either = count + rhs.bit_count() - both;
+		if either == 0 { 0.0 } else { both as f64 / either as f64 }
+	}
+	/// Dice coefficient, twice the number of bits set in both divided by the sum of the number of bits set in each.
+	///
+	/// Returns `0.0` if neither has any bits set.
+	/// Panics if the lengths differ.
+	#[inline]
+	fn bit_dice(&self, rhs: &Self) -> f64 {
+		let count = self.bit_count();
+		let both = count - self.bit_andnot_count(rhs);
+		let total = count + rhs.bit_count();
+		if total == 0 { 0.0 } else { (2 * both) as f64 / total as f64 }
+	}
+	/// Sums the weights of the bits set in both self and rhs.
+	///
+	/// The integer, slice, simd and `BitArray` implementations only visit the bits in the intersection, so they are cheap for sparse overlaps.
+	/// Panics if the lengths differ or `weights.len()` is not `bit_len()`.
+	#[inline]
+	fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+		let len = self.bit_len();
+		assert_eq!(len, rhs.bit_len());
+		assert_eq!(len, weights.len());
+		let mut result = 0;
+		for (i, &weight) in weights.iter().enumerate() {
+			if self.bit_test(i) && rhs.bit_test(i) {
+				result += weight as u64;
+			}
+		}
+		result
+	}
+	/// Counts the number of consecutive set bits starting from the highest bit.
+	#[inline]
+	fn bit_leading_ones(&self) -> usize {
+		(0..self.bit_len()).rev().take_while(|&i| self.bit_test(i)).count()
+	}
+	/// Counts the number of consecutive set bits starting from bit 0.
+	#[inline]
+	fn bit_trailing_ones(&self) -> usize {
+		(0..self.bit_len()).take_while(|&i| self.bit_test(i)).count()
+	}
+
+	/// Counts the set bits and finds the lowest and highest set bit in a single pass.
+	#[inline]
+	fn bit_summary(&self) -> BitSummary {
+		let mut summary = BitSummary::default();
+		for i in 0..self.bit_len() {
+			if self.bit_test(i) {
+				summary.count += 1;
+				summary.first = summary.first.or(Some(i));
+				summary.last = Some(i);
+			}
+		}
+		summary
+	}
+
+	/// Writes the number of set bits before every backing word into `out`.
+	///
+	/// `out[i]` is the number of set bits in the words before word `i` and the final entry is the total count.
+	/// Panics if `out.len()` is not the number of backing words plus one.
+	///
+	/// Containers without integer backing words use groups of 64 bits.
+	#[inline]
+	fn bit_rank_table(&self, out: &mut [usize]) {
+		let len = self.bit_len();
+		assert_eq!(out.len(), len.div_ceil(64) + 1);
+		let mut rank = 0;
+		for i in 0..len {
+			if i % 64 == 0 {
+				out[i / 64] = rank;
+			}
+			rank += self.bit_test(i) as usize;
+		}
+		out[out.len() - 1] = rank;
+	}
+	/// Builds a rank and select directory over the bits.
+	#[cfg(feature = "std")]
+	#[inline]
+	fn bit_rank_select(&self) -> RankSelect<'_, Self> {
+		RankSelect::new(self)
+	}
+
+	/// Loads the backing words from bytes in the given byte order.
+	///
+	/// Returns an error without modifying self if the number of bytes does not match the size of the backing words.
+	///
+	/// Containers without integer backing words are loaded as groups of 64 bits, bits beyond `bit_len` are ignored.
+	#[inline]
+	fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+		let len = self.bit_len();
+		if bytes.len() != len.div_ceil(64) * 8 {
+			return Err(LengthError);
+		}
+		for i in 0..len {
+			let byte = bytes[byte_index(i, endian)];
+			self.bit_cond(i, byte >> (i % 8) & 1 != 0);
+		}
+		Ok(())
+	}
+	/// Stores the backing words as bytes in the given byte order.
+	///
+	/// Returns an error without writing anything if the number of bytes does not match the size of the backing words.
+	///
+	/// Containers without integer backing words are stored as groups of 64 bits, bits beyond `bit_len` are stored cleared.
+	#[inline]
+	fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+		let len = self.bit_len();
+		if out.len() != len.div_ceil(64) * 8 {
+			return Err(LengthError);
+		}
+		for byte in out.iter_mut() {
+			*byte = 0;
+		}
+		for i in 0..len {
+			out[byte_index(i, endian)] |= (self.bit_test(i) as u8) << (i % 8);
+		}
+		Ok(())
+	}
+
+	/// Counts the number of backing words with any bits set.
+	///
+	/// The words are those visited by `bit_fold_words`.
+	#[inline]
+	fn bit_nonzero_words(&self) -> usize {
+		self.bit_fold_words(0, |n, _, word| n + (word != 0) as usize)
+	}
+	/// Returns the index of the first backing word with any bits set.
+	///
+	/// The words are those visited by `bit_fold_words`.
+	#[inline]
+	fn bit_first_nonzero_word(&self) -> Option<usize> {
+		(0..self.bit_len()).find(|&i| self.bit_test(i)).map(|i| i / 64)
+	}
+	/// Folds over the backing words with their index, narrow words are zero-extended to `u64`.
+	///
+	/// Words wider than 64 bits are visited as their 64 bit halves, low half first, and containers without integer backing words as groups of 64 bits.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0x0fu8, 0x00, 0x81];
+	/// let checksum = bits.bit_fold_words(0, |acc, index, word| acc ^ word << index);
+	/// assert_eq!(checksum, 0x0f ^ 0x81 << 2);
+	/// ```
+	#[inline]
+	fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, mut f: F) -> B {
+		let len = self.bit_len();
+		let mut acc = init;
+		for (index, start) in (0..len).step_by(64).enumerate() {
+			let mut word = 0u64;
+			for i in start..core::cmp::min(start + 64, len) {
+				word |= (self.bit_test(i) as u64) << (i - start);
+			}
+			acc = f(acc, index, word);
+		}
+		acc
+	}
+	/// Returns the index and population count of the word with the most set bits, the first one on ties.
+	///
+	/// The words are those visited by `bit_fold_words`, returns `None` if no bits are set.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0x01u8, 0x0f, 0xf0, 0x03];
+	/// assert_eq!(bits.bit_densest_word(), Some((1, 4)));
+	/// assert_eq!(bits.bit_sparsest_nonzero_word(), Some((0, 1)));
+	/// ```
+	#[inline]
+	fn bit_densest_word(&self) -> Option<(usize, u32)> {
+		self.bit_fold_words(None, |best, index, word| {
+			let count = word.count_ones();
+			match best {
+				Some((_, most)) if most >= count => best,
+				_ if count == 0 => best,
+				_ => Some((index, count)),
+			}
+		})
+	}
+	/// Returns the index and population count of the word with the fewest set bits among those with any bits set, the first one on ties.
+	///
+	/// The words are those visited by `bit_fold_words`, returns `None` if no bits are set.
+	#[inline]
+	fn bit_sparsest_nonzero_word(&self) -> Option<(usize, u32)> {
+		self.bit_fold_words(None, |best, index, word| {
+			let count = word.count_ones();
+			match best {
+				_ if count == 0 => best,
+				Some((_, fewest)) if fewest <= count => best,
+				_ => Some((index, count)),
+			}
+		})
+	}
+
+	/// Returns the index of the first cleared bit.
+	///
+	/// Returns `None` if all bits are set. Never returns an index at or beyond `bit_len()`.
+	#[inline]
+	fn bit_find_first_zero(&self) -> Option<usize> {
+		self.bit_find_next_zero(0)
+	}
+	/// Returns the index of the first cleared bit at or after `from`.
+	///
+	/// Returns `None` if all bits from `from` onwards are set.
+	#[inline]
+	fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+		(from..self.bit_len()).find(|&i| !self.bit_test(i))
+	}
+	/// Returns the index of the `k + 1`th set bit counting down from the highest bit.
+	///
+	/// Returns `None` if there are not more than `k` set bits.
+	///
+	/// ```
+	/// use bitset_core::BitSet;
+	///
+	/// let bits = [0x0000_0101u32, 0x8000_0000];
+	/// assert_eq!(bits.bit_select_high(0), Some(63));
+	/// assert_eq!(bits.bit_select_high(2), Some(0));
+	/// assert_eq!(bits.bit_select_high(3), None);
+	/// ```
+	#[inline]
+	fn bit_select_high(&self, k: usize) -> Option<usize> {
+		(0..self.bit_len()).rev().filter(|&i| self.bit_test(i)).nth(k)
+	}
+
+	/// Downsamples into `out` where each bit is the OR of `factor` consecutive bits.
+	///
+	/// Panics if `out.bit_len() * factor != self.bit_len()`.
+	#[inline]
+	fn bit_downsample_or(&self, factor: usize, out: &mut Self) {
+		assert_eq!(out.bit_len() * factor, self.bit_len());
+		for i in 0..out.bit_len() {
+			let mut value = false;
+			for j in 0..factor {
+				value |= self.bit_test(i * factor + j);
+			}
+			out.bit_cond(i, value);
+		}
+	}
+	/// Upsamples into `out` where each bit is replicated `factor` times.
+	///
+	/// Panics if `self.bit_len() * factor != out.bit_len()`.
+	#[inline]
+	fn bit_upsample(&self, factor: usize, out: &mut Self) {
+		assert_eq!(self.bit_len() * factor, out.bit_len());
+		for i in 0..self.bit_len() {
+			let value = self.bit_test(i);
+			for j in 0..factor {
+				out.bit_cond(i * factor + j, value);
+			}
+		}
+	}
+	/// Spreads the bits into `out` where bit `i` moves to bit `i * stride` and the bits in between are reset.
+	///
+	/// Panics if `self.bit_len() * stride != out.bit_len()`.
+	#[inline]
+	fn bit_expand_into(&self, stride: usize, out: &mut Self) {
+		assert_eq!(self.bit_len() * stride, out.bit_len());
+		out.bit_init(false);
+		for i in 0..self.bit_len() {
+			out.bit_cond(i * stride, self.bit_test(i));
+		}
+	}
+	/// Collects every `stride`th bit into `out`, the inverse of `bit_expand_into`.
+	///
+	/// Panics if `out.bit_len() * stride != self.bit_len()`.
+	#[inline]
+	fn bit_compress_into(&self, stride: usize, out: &mut Self) {
+		assert_eq!(out.bit_len() * stride, self.bit_len());
+		for i in 0..out.bit_len() {
+			out.bit_cond(i, self.bit_test(i * stride));
+		}
+	}
+}
+
+/// Binary operation for `BitSet::bit_combine_into`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BitOp {
+	/// Bitwise OR, the tail is copied from the longer operand.
+	Or,
+	/// Bitwise AND, the tail is zeroed.
+	And,
+	/// Bitwise AND after NOT of `b`, the tail is copied from `a` if it is the longer operand and zeroed otherwise.
+	AndNot,
+	/// Bitwise XOR, the tail is copied from the longer operand.
+	Xor,
+}
+
+/// Population count and the lowest and highest set bits, see `BitSet::bit_summary`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitSummary {
+	/// Number of set bits.
+	pub count: usize,
+	/// Index of the lowest set bit.
+	pub first: Option<usize>,
+	/// Index of the highest set bit.
+	pub last: Option<usize>,
 }
 
+/// Byte order of the backing words for `BitSet::bit_load_bytes` and `BitSet::bit_store_bytes`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Endian {
+	/// Least significant byte first.
+	Little,
+	/// Most significant byte first.
+	Big,
+}
+
+/// Error when a buffer does not match the size of the bitset.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct LengthError;
+
+impl core::fmt::Display for LengthError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str("buffer length does not match the bitset")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
 /// Shorthand for setting bits on the bitset container.
 ///
 /// Returns the value of the initial argument after setting the bits.
+/// Besides individual bits the arguments may be ranges `lo..hi` or `lo..=hi` whose bounds are literals, identifiers or parenthesized expressions.
 ///
 /// ```
 /// use bitset_core::{bitset, BitSet};
 /// let bits = bitset!([0u8; 4]; 2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31);
 /// assert_eq!(bits.bit_count(), 11);
+///
+/// let bits = bitset!(0u16; 1, 3..6, 9, 12..=13);
+/// assert_eq!(bits, 0x323a);
 /// ```
 #[macro_export]
 macro_rules! bitset {
-	($init:expr; $($bit:expr),* $(,)?) => {{
+	(@set $this:ident;) => {};
+	(@set $this:ident; $lo:tt .. $hi:tt $(, $($rest:tt)*)?) => {
+		$this.bit_set_range($lo as usize..$hi as usize);
+		$crate::bitset!(@set $this; $($($rest)*)?);
+	};
+	(@set $this:ident; $lo:tt ..= $hi:tt $(, $($rest:tt)*)?) => {
+		$this.bit_set_range($lo as usize..$hi as usize + 1);
+		$crate::bitset!(@set $this; $($($rest)*)?);
+	};
+	(@set $this:ident; $bit:expr $(, $($rest:tt)*)?) => {
+		$this.bit_set($bit as usize);
+		$crate::bitset!(@set $this; $($($rest)*)?);
+	};
+	($init:expr; $($args:tt)*) => {{
 		use $crate::BitSet;
 		#[allow(unused_mut)]
 		match $init {
 			mut this => {
-				$(this.bit_set($bit as usize);)*
+				$crate::bitset!(@set this; $($args)*);
 				this
 			},
 		}
@@ -237,14 +1439,52 @@ macro_rules! impl_bitset {
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_cond(<Self as ops::DerefMut>::deref_mut(self), bit, value);
 			self
 		}
-
 		#[inline]
-		fn bit_all(&self) -> bool {
+		fn bit_test_and_set(&mut self, bit: usize) -> bool {
 			use ::core::ops;
-			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_all(<Self as ops::Deref>::deref(self))
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_test_and_set(<Self as ops::DerefMut>::deref_mut(self), bit)
 		}
 		#[inline]
-		fn bit_any(&self) -> bool {
+		fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_test_and_reset(<Self as ops::DerefMut>::deref_mut(self), bit)
+		}
+		#[inline]
+		fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_test_and_flip(<Self as ops::DerefMut>::deref_mut(self), bit)
+		}
+		#[inline]
+		fn bit_cond_range(&mut self, range: ::core::ops::Range<usize>, value: bool) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_cond_range(<Self as ops::DerefMut>::deref_mut(self), range, value);
+			self
+		}
+
+		#[inline]
+		fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_set_bits(<Self as ops::DerefMut>::deref_mut(self), indices);
+			self
+		}
+		#[inline]
+		fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_test_at(<Self as ops::Deref>::deref(self), word, bit_in_word)
+		}
+		#[inline]
+		fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_set_at(<Self as ops::DerefMut>::deref_mut(self), word, bit_in_word);
+			self
+		}
+		#[inline]
+		fn bit_all(&self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_all(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_any(&self) -> bool {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_any(<Self as ops::Deref>::deref(self))
 		}
@@ -253,7 +1493,27 @@ macro_rules! impl_bitset {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_none(<Self as ops::Deref>::deref(self))
 		}
+		#[inline]
+		fn bit_is_empty(&self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_is_empty(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_is_full(&self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_is_full(<Self as ops::Deref>::deref(self))
+		}
 
+		#[inline]
+		fn bit_all_len(&self, len: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_all_len(<Self as ops::Deref>::deref(self), len)
+		}
+		#[inline]
+		fn bit_any_len(&self, len: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_any_len(<Self as ops::Deref>::deref(self), len)
+		}
 		#[inline]
 		fn bit_eq(&self, rhs: &Self) -> bool {
 			use ::core::ops;
@@ -265,6 +1525,11 @@ macro_rules! impl_bitset {
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_disjoint(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs))
 		}
 		#[inline]
+		fn bit_disjoint_range(&self, rhs: &Self, range: ::core::ops::Range<usize>) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_disjoint_range(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), range)
+		}
+		#[inline]
 		fn bit_subset(&self, rhs: &Self) -> bool {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_subset(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs))
@@ -274,6 +1539,21 @@ macro_rules! impl_bitset {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_superset(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs))
 		}
+		#[inline]
+		fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_eq_masked(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), <Self as ops::Deref>::deref(mask))
+		}
+		#[inline]
+		fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_subset_masked(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), <Self as ops::Deref>::deref(mask))
+		}
+		#[inline]
+		fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_missing_from(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(available), <Self as ops::DerefMut>::deref_mut(out))
+		}
 
 		#[inline]
 		fn bit_or(&mut self, rhs: &Self) -> &mut Self {
@@ -294,6 +1574,27 @@ macro_rules! impl_bitset {
 			self
 		}
 		#[inline]
+		fn bit_andnot_range(&mut self, rhs: &Self, range: ::core::ops::Range<usize>) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_andnot_range(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs), range);
+			self
+		}
+		#[inline]
+		fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_andnot_changed(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs))
+		}
+		#[inline]
+		fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_or_delta(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs))
+		}
+		#[inline]
+		fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_andnot_delta(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs))
+		}
+		#[inline]
 		fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_xor(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs));
@@ -306,44 +1607,343 @@ macro_rules! impl_bitset {
 			self
 		}
 		#[inline]
+		fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_not_cond(<Self as ops::DerefMut>::deref_mut(self), condition);
+			self
+		}
+		#[inline]
 		fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_mask(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs), <Self as ops::Deref>::deref(mask));
 			self
 		}
+		#[inline]
+		fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_mask_inv(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs), <Self as ops::Deref>::deref(mask));
+			self
+		}
+		#[inline]
+		fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_or_and(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs), <Self as ops::Deref>::deref(cond));
+			self
+		}
+		#[inline]
+		fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_blend_into(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(a), <Self as ops::Deref>::deref(b), <Self as ops::Deref>::deref(mask));
+			self
+		}
+		#[inline]
+		fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_or_broadcast(<Self as ops::DerefMut>::deref_mut(self), word);
+			self
+		}
+		#[inline]
+		fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_and_broadcast(<Self as ops::DerefMut>::deref_mut(self), word);
+			self
+		}
+		#[inline]
+		fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_xor_broadcast(<Self as ops::DerefMut>::deref_mut(self), word);
+			self
+		}
+		#[inline]
+		fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_insert(<Self as ops::DerefMut>::deref_mut(self), index, value);
+			self
+		}
+		#[inline]
+		fn bit_remove(&mut self, index: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_remove(<Self as ops::DerefMut>::deref_mut(self), index)
+		}
+		#[inline]
+		fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_shl_len(<Self as ops::DerefMut>::deref_mut(self), n, len);
+			self
+		}
+		#[inline]
+		fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_shl_fill(<Self as ops::DerefMut>::deref_mut(self), n, fill);
+			self
+		}
+		#[inline]
+		fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_shr_fill(<Self as ops::DerefMut>::deref_mut(self), n, fill);
+			self
+		}
+		#[inline]
+		fn bit_reverse_range(&mut self, range: ::core::ops::Range<usize>) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_reverse_range(<Self as ops::DerefMut>::deref_mut(self), range);
+			self
+		}
+		#[inline]
+		fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_reverse_groups(<Self as ops::DerefMut>::deref_mut(self), group_bits);
+			self
+		}
+		#[inline]
+		fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_swap_ranges(<Self as ops::DerefMut>::deref_mut(self), a, b, len);
+			self
+		}
+		#[inline]
+		fn bit_combine_into(&mut self, a: &Self, b: &Self, op: $crate::BitOp) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_combine_into(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(a), <Self as ops::Deref>::deref(b), op);
+			self
+		}
 
+		#[inline]
+		fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_and_tiled(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(pattern));
+			self
+		}
+		#[inline]
+		fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_or_tiled(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(pattern));
+			self
+		}
+		#[inline]
+		fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_xor_tiled(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(pattern));
+			self
+		}
+		#[inline]
+		fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_or_word_set(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs));
+			self
+		}
+		#[inline]
+		fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_and_word_set(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs));
+			self
+		}
+		#[inline]
+		fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_xor_word_set(<Self as ops::DerefMut>::deref_mut(self), <Self as ops::Deref>::deref(rhs));
+			self
+		}
 		#[inline]
 		fn bit_count(&self) -> usize {
 			use ::core::ops;
 			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_count(<Self as ops::Deref>::deref(self))
 		}
+		#[inline]
+		fn bit_count_up_to(&self, limit: usize) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_count_up_to(<Self as ops::Deref>::deref(self), limit)
+		}
+		#[inline]
+		fn bit_andnot_count(&self, rhs: &Self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_andnot_count(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs))
+		}
+		#[inline]
+		fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_and_count_at_least(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), threshold)
+		}
+		#[inline]
+		fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_hamming_at_most(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), max)
+		}
+		#[inline]
+		fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_weighted_overlap(<Self as ops::Deref>::deref(self), <Self as ops::Deref>::deref(rhs), weights)
+		}
+		#[inline]
+		fn bit_leading_ones(&self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_leading_ones(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_trailing_ones(&self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_trailing_ones(<Self as ops::Deref>::deref(self))
+		}
+
+		#[inline]
+		fn bit_find_first_zero(&self) -> Option<usize> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_find_first_zero(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_find_next_zero(<Self as ops::Deref>::deref(self), from)
+		}
+		#[inline]
+		fn bit_select_high(&self, k: usize) -> Option<usize> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_select_high(<Self as ops::Deref>::deref(self), k)
+		}
+		#[inline]
+		fn bit_summary(&self) -> $crate::BitSummary {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_summary(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_rank_table(&self, out: &mut [usize]) {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_rank_table(<Self as ops::Deref>::deref(self), out)
+		}
+
+		#[inline]
+		fn bit_load_bytes(&mut self, bytes: &[u8], endian: $crate::Endian) -> Result<(), $crate::LengthError> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_load_bytes(<Self as ops::DerefMut>::deref_mut(self), bytes, endian)
+		}
+		#[inline]
+		fn bit_store_bytes(&self, out: &mut [u8], endian: $crate::Endian) -> Result<(), $crate::LengthError> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_store_bytes(<Self as ops::Deref>::deref(self), out, endian)
+		}
+
+		#[inline]
+		fn bit_nonzero_words(&self) -> usize {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_nonzero_words(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_first_nonzero_word(&self) -> Option<usize> {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_first_nonzero_word(<Self as ops::Deref>::deref(self))
+		}
+		#[inline]
+		fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+			use ::core::ops;
+			<<Self as ops::Deref>::Target as $crate::BitSet>::bit_fold_words(<Self as ops::Deref>::deref(self), init, f)
+		}
 	};
 }
 
 mod uint;
 mod slice;
+mod bools;
 mod simd;
+pub use self::simd::SimdBitSet;
+
+mod align;
+pub use self::align::{Aligned16, Aligned32, Aligned64};
+
+mod array;
+pub use self::array::BitArray;
+
+mod bitref;
+pub use self::bitref::BitRef;
+
+mod counted;
+pub use self::counted::CountedBitSet;
 
 #[cfg(feature = "std")]
 mod stdty;
+#[cfg(feature = "std")]
+pub use self::stdty::{bit_accumulate, GrowableBitSet};
+
+#[cfg(feature = "std")]
+mod matrix;
+#[cfg(feature = "std")]
+pub use self::matrix::BitMatrix;
+
+#[cfg(feature = "std")]
+mod rank;
+#[cfg(feature = "std")]
+pub use self::rank::RankSelect;
 
 mod fmt;
-pub use self::fmt::BitFmt;
+pub use self::fmt::{BitFmt, BitFmtMsb, BitFmtTruncated};
+
+mod iter;
+pub use self::iter::{BitBytes, BitChanges};
+
+mod builder;
+pub use self::builder::BitBuilder;
+
+mod object;
+pub use self::object::DynBitSet;
+
+mod cell;
+pub use self::cell::CellBitSet;
+
+mod funcs;
+pub use self::funcs::{bit_and_all, bit_and_from, bit_count_slice_u32, bit_count_slice_u64, bit_difference, bit_eq_words, bit_intersection, bit_or_all, bit_or_from, bit_or_reduce, bit_symmetric_difference, bit_transcode, bit_union, bit_xor_from, bit_xor_into};
+
+// Combines the bits with the pattern repeated to cover the length of bits.
+fn tiled<T: ?Sized + BitSet>(bits: &mut T, pattern: &T, op: BitOp) {
+	let period = pattern.bit_len();
+	assert!(period > 0, "empty tiling pattern");
+	for i in 0..bits.bit_len() {
+		let x = bits.bit_test(i);
+		let y = pattern.bit_test(i % period);
+		let value = match op {
+			BitOp::Or => x | y,
+			BitOp::And => x & y,
+			BitOp::AndNot => x & !y,
+			BitOp::Xor => x ^ y,
+		};
+		bits.bit_cond(i, value);
+	}
+}
+
+// Index of the byte holding the bit when storing groups of 64 bits in the given byte order.
+#[inline]
+fn byte_index(bit: usize, endian: Endian) -> usize {
+	let word = bit / 64 * 8;
+	match endian {
+		Endian::Little => word + bit % 64 / 8,
+		Endian::Big => word + 7 - bit % 64 / 8,
+	}
+}
 
 //----------------------------------------------------------------
 
+// Deterministic xorshift generator for the randomized tests
+#[cfg(test)]
+fn xorshift(seed: u32) -> impl FnMut() -> u32 {
+	let mut state = seed;
+	move || {
+		state ^= state << 13;
+		state ^= state >> 17;
+		state ^= state << 5;
+		state
+	}
+}
+
 #[cfg(test)]
 fn unary_tests<T: ?Sized + BitSet>(bits: &mut T) {
 	// reset all bits
 	bits.bit_init(false);
-	assert_eq!(bits.bit_any(), false);
-	assert_eq!(bits.bit_all(), false);
+	assert!(!bits.bit_any());
+	assert!(!bits.bit_all());
 	// set even bits
 	for i in 0..bits.bit_len() {
 		bits.bit_set(i & !1);
 	}
-	assert_eq!(bits.bit_any(), true);
-	assert_eq!(bits.bit_all(), false);
+	assert!(bits.bit_any());
+	assert!(!bits.bit_all());
 	for i in 0..bits.bit_len() {
 		assert_eq!(bits.bit_test(i), i & 1 == 0);
 	}
@@ -356,14 +1956,14 @@ fn unary_tests<T: ?Sized + BitSet>(bits: &mut T) {
 
 	// set all bits
 	bits.bit_init(true);
-	assert_eq!(bits.bit_any(), true);
-	assert_eq!(bits.bit_all(), true);
+	assert!(bits.bit_any());
+	assert!(bits.bit_all());
 	// clear even bits
 	for i in 0..bits.bit_len() {
 		bits.bit_reset(i & !1);
 	}
-	assert_eq!(bits.bit_any(), true);
-	assert_eq!(bits.bit_all(), false);
+	assert!(bits.bit_any());
+	assert!(!bits.bit_all());
 	for i in 0..bits.bit_len() {
 		assert_eq!(bits.bit_test(i), i & 1 != 0);
 	}
@@ -378,3 +1978,156 @@ fn unary_tests<T: ?Sized + BitSet>(bits: &mut T) {
 	assert!(bits.bit_subset(bits));
 	assert!(bits.bit_superset(bits));
 }
+
+// Bitset implementing only the required methods, exercising the default implementations
+#[cfg(test)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct MinimalBitSet([bool; 150]);
+
+#[cfg(test)]
+impl BitSet for MinimalBitSet {
+	fn bit_len(&self) -> usize {
+		self.0.len()
+	}
+	fn bit_init(&mut self, value: bool) -> &mut Self {
+		self.0.bit_init(value);
+		self
+	}
+	fn bit_test(&self, bit: usize) -> bool {
+		self.0[bit]
+	}
+	fn bit_set(&mut self, bit: usize) -> &mut Self {
+		self.0[bit] = true;
+		self
+	}
+	fn bit_reset(&mut self, bit: usize) -> &mut Self {
+		self.0[bit] = false;
+		self
+	}
+	fn bit_flip(&mut self, bit: usize) -> &mut Self {
+		self.0[bit] = !self.0[bit];
+		self
+	}
+	fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+		self.0[bit] = value;
+		self
+	}
+	fn bit_all(&self) -> bool {
+		self.0.bit_all()
+	}
+	fn bit_any(&self) -> bool {
+		self.0.bit_any()
+	}
+	fn bit_eq(&self, rhs: &Self) -> bool {
+		self.0 == rhs.0
+	}
+	fn bit_disjoint(&self, rhs: &Self) -> bool {
+		self.0.bit_disjoint(&rhs.0)
+	}
+	fn bit_subset(&self, rhs: &Self) -> bool {
+		self.0.bit_subset(&rhs.0)
+	}
+	fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+		self.0.bit_or(&rhs.0);
+		self
+	}
+	fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+		self.0.bit_and(&rhs.0);
+		self
+	}
+	fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+		self.0.bit_andnot(&rhs.0);
+		self
+	}
+	fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+		self.0.bit_xor(&rhs.0);
+		self
+	}
+	fn bit_not(&mut self) -> &mut Self {
+		self.0.bit_not();
+		self
+	}
+	fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+		self.0.bit_mask(&rhs.0, &mask.0);
+		self
+	}
+	fn bit_count(&self) -> usize {
+		self.0.bit_count()
+	}
+}
+
+#[test]
+fn test_minimal() {
+	let mut bits = MinimalBitSet([false; 150]);
+	unary_tests(&mut bits);
+
+	// Words are groups of 64 bits
+	bits.bit_init(false);
+	assert_eq!(bits.bit_nonzero_words(), 0);
+	assert_eq!(bits.bit_first_nonzero_word(), None);
+	bits.bit_set(70).bit_set(149);
+	assert_eq!(bits.bit_nonzero_words(), 2);
+	assert_eq!(bits.bit_first_nonzero_word(), Some(1));
+	bits.bit_set(3);
+	assert_eq!(bits.bit_nonzero_words(), 3);
+	assert_eq!(bits.bit_first_nonzero_word(), Some(0));
+
+	// Broadcasts tile the word over the groups of 64 bits
+	let word = 0x8000_0000_0000_0005;
+	bits.bit_init(false).bit_or_broadcast(word);
+	for i in 0..150 {
+		assert_eq!(bits.bit_test(i), i % 64 == 0 || i % 64 == 2 || i % 64 == 63);
+	}
+	bits.bit_xor_broadcast(!0);
+	assert_eq!(bits.bit_count(), 150 - 8);
+	bits.bit_and_broadcast(0x6);
+	for i in 0..150 {
+		assert_eq!(bits.bit_test(i), i % 64 == 1);
+	}
+
+	// Bytes are stored as groups of 64 bits in the given byte order
+	let mut words = [0u64; 3];
+	words[..].bit_set(0).bit_set(12).bit_set(70).bit_set(149);
+	bits.bit_init(false).bit_set(0).bit_set(12).bit_set(70).bit_set(149);
+	let mut bytes = [0xffu8; 24];
+	assert_eq!(bits.bit_store_bytes(&mut bytes, Endian::Little), Ok(()));
+	assert_eq!(&bytes[..8], &words[0].to_le_bytes());
+	assert_eq!(&bytes[16..], &words[2].to_le_bytes());
+	assert_eq!(bits.bit_store_bytes(&mut bytes, Endian::Big), Ok(()));
+	assert_eq!(&bytes[8..16], &words[1].to_be_bytes());
+	let mut loaded = MinimalBitSet([true; 150]);
+	assert_eq!(loaded.bit_load_bytes(&bytes, Endian::Big), Ok(()));
+	assert_eq!(loaded, bits);
+
+	// Garbage beyond the bits is ignored
+	bytes[16..].copy_from_slice(&(!0u64 << 22).to_be_bytes());
+	assert_eq!(loaded.bit_load_bytes(&bytes, Endian::Big), Ok(()));
+	assert_eq!(loaded.bit_count(), 3);
+
+	assert_eq!(bits.bit_store_bytes(&mut [0; 16], Endian::Little), Err(LengthError));
+	assert_eq!(loaded.bit_load_bytes(&[0; 25], Endian::Little), Err(LengthError));
+	assert_eq!(loaded.bit_count(), 3);
+
+	// Rank table over the groups of 64 bits
+	let mut table = [!0; 4];
+	bits.bit_rank_table(&mut table);
+	assert_eq!(table, [0, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic]
+fn test_minimal_rank_table_len() {
+	MinimalBitSet([false; 150]).bit_rank_table(&mut [0; 150 + 1]);
+}
+
+#[test]
+fn test_bitset_macro() {
+	let lo = 20;
+	let bits = bitset!([0u32; 4]; 0, 3..6, lo..(lo + 10), 64..=64, 100, (lo * 5 + 10)..128,);
+	let mut expected = [0u32; 4];
+	expected.bit_set(0).bit_set_range(3..6).bit_set_range(20..30).bit_set(64).bit_set(100).bit_set_range(110..128);
+	assert_eq!(bits, expected);
+
+	assert_eq!(bitset!(0u8; 0..8), 0xff);
+	assert_eq!(bitset!(0u8; 1 + 1, 4..4), 0x04);
+}
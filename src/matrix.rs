@@ -0,0 +1,127 @@
+use std::vec::Vec;
+use super::BitSet;
+
+/// Matrix of bits stored as a list of row bitsets.
+///
+/// Row `i` column `j` is bit `j` of the `i`th row.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitMatrix<R> {
+	rows: Vec<R>,
+}
+
+impl<R: BitSet> BitMatrix<R> {
+	/// Creates a matrix from its rows.
+	///
+	/// Panics if the rows are not all the same length.
+	#[inline]
+	pub fn new(rows: Vec<R>) -> BitMatrix<R> {
+		if let Some(first) = rows.first() {
+			let len = first.bit_len();
+			assert!(rows.iter().all(|row| row.bit_len() == len));
+		}
+		BitMatrix { rows }
+	}
+	/// Returns the rows of the matrix.
+	#[inline]
+	pub fn into_rows(self) -> Vec<R> {
+		self.rows
+	}
+	/// Returns the rows of the matrix.
+	#[inline]
+	pub fn rows(&self) -> &[R] {
+		&self.rows
+	}
+
+	/// Returns the number of rows.
+	#[inline]
+	pub fn row_count(&self) -> usize {
+		self.rows.len()
+	}
+	/// Returns the number of columns.
+	#[inline]
+	pub fn column_count(&self) -> usize {
+		self.rows.first().map_or(0, |row| row.bit_len())
+	}
+
+	/// Returns the given row.
+	#[inline]
+	pub fn row(&self, row: usize) -> &R {
+		&self.rows[row]
+	}
+	/// Returns the given row.
+	#[inline]
+	pub fn row_mut(&mut self, row: usize) -> &mut R {
+		&mut self.rows[row]
+	}
+
+	/// Returns if the bit at the given row and column is set.
+	#[inline]
+	pub fn test(&self, row: usize, column: usize) -> bool {
+		self.rows[row].bit_test(column)
+	}
+	/// Returns if none of the bits in the given column are set.
+	#[inline]
+	pub fn column_none(&self, column: usize) -> bool {
+		self.rows.iter().all(|row| !row.bit_test(column))
+	}
+
+	/// Bitwise OR of the `src` row into the `dst` row.
+	#[inline]
+	pub fn or_row_into(&mut self, dst: usize, src: usize) {
+		if dst < src {
+			let (head, tail) = self.rows.split_at_mut(src);
+			head[dst].bit_or(&tail[0]);
+		}
+		else if dst > src {
+			let (head, tail) = self.rows.split_at_mut(dst);
+			tail[0].bit_or(&head[src]);
+		}
+	}
+
+	/// Returns the transposed matrix.
+	///
+	/// Panics if the matrix is not square as the rows cannot be resized.
+	pub fn transpose(&self) -> BitMatrix<R> where R: Clone {
+		let n = self.row_count();
+		assert_eq!(n, self.column_count());
+		let mut rows = self.rows.clone();
+		for row in &mut rows {
+			row.bit_init(false);
+		}
+		for (i, src) in self.rows.iter().enumerate() {
+			for (j, dst) in rows.iter_mut().enumerate() {
+				if src.bit_test(j) {
+					dst.bit_set(i);
+				}
+			}
+		}
+		BitMatrix { rows }
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn test_transitive_closure() {
+	// Edges 0 -> 1 -> 2 -> 3, 5 -> 6 -> 5 and 7 -> 0
+	let mut graph = BitMatrix::new(vec![0b10u8, 0b100, 0b1000, 0, 0, 0b100_0000, 0b10_0000, 0b1]);
+	assert_eq!(graph.row_count(), 8);
+	assert_eq!(graph.column_count(), 8);
+	assert!(graph.column_none(4));
+	assert!(graph.column_none(7));
+
+	// Warshall's algorithm through repeated row ORs
+	for k in 0..8 {
+		for i in 0..8 {
+			if graph.test(i, k) {
+				graph.or_row_into(i, k);
+			}
+		}
+	}
+	assert_eq!(graph.rows(), &[0b1110, 0b1100, 0b1000, 0, 0, 0b110_0000, 0b110_0000, 0b1111]);
+
+	let reached_by = graph.transpose();
+	assert_eq!(*reached_by.row(3), 0b1000_0111);
+	assert_eq!(*reached_by.row(5), 0b0110_0000);
+	assert_eq!(reached_by.transpose(), graph);
+}
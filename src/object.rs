@@ -0,0 +1,86 @@
+use super::BitSet;
+
+/// Object safe subset of the `BitSet` API.
+///
+/// `BitSet` itself cannot be made into a trait object as the binary operations take `&Self` and the mutating methods return `&mut Self`.
+/// This trait is implemented for every `BitSet` and exposes the read and single bit methods so heterogeneous bitsets can be stored as `Box<dyn DynBitSet>`.
+/// The methods carry a `dyn_` prefix so they do not clash with the `BitSet` methods when both traits are in scope.
+///
+/// ```
+/// use bitset_core::DynBitSet;
+///
+/// let mut sets: Vec<Box<dyn DynBitSet>> = vec![Box::new(0xf0u8), Box::new(3u64)];
+/// sets[1].dyn_bit_set(40);
+/// assert_eq!(sets.iter().map(|set| set.dyn_bit_count()).sum::<usize>(), 7);
+/// ```
+pub trait DynBitSet {
+	/// Returns total number of bits.
+	fn dyn_bit_len(&self) -> usize;
+	/// Returns if the given bit is set.
+	fn dyn_bit_test(&self, bit: usize) -> bool;
+	/// Sets the given bit.
+	fn dyn_bit_set(&mut self, bit: usize);
+	/// Resets the given bit.
+	fn dyn_bit_reset(&mut self, bit: usize);
+	/// Counts the number of set bits.
+	fn dyn_bit_count(&self) -> usize;
+	/// Returns if any bits are set.
+	fn dyn_bit_any(&self) -> bool;
+	/// Returns if all bits are set.
+	fn dyn_bit_all(&self) -> bool;
+}
+
+impl<T: ?Sized + BitSet> DynBitSet for T {
+	#[inline]
+	fn dyn_bit_len(&self) -> usize {
+		BitSet::bit_len(self)
+	}
+	#[inline]
+	fn dyn_bit_test(&self, bit: usize) -> bool {
+		BitSet::bit_test(self, bit)
+	}
+	#[inline]
+	fn dyn_bit_set(&mut self, bit: usize) {
+		BitSet::bit_set(self, bit);
+	}
+	#[inline]
+	fn dyn_bit_reset(&mut self, bit: usize) {
+		BitSet::bit_reset(self, bit);
+	}
+	#[inline]
+	fn dyn_bit_count(&self) -> usize {
+		BitSet::bit_count(self)
+	}
+	#[inline]
+	fn dyn_bit_any(&self) -> bool {
+		BitSet::bit_any(self)
+	}
+	#[inline]
+	fn dyn_bit_all(&self) -> bool {
+		BitSet::bit_all(self)
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	use std::boxed::Box;
+	use super::BitArray;
+
+	let mut sets: std::vec::Vec<Box<dyn DynBitSet>> = vec![
+		Box::new(0x8001u16),
+		Box::new(1u128),
+		Box::new(BitArray::<100, 2>::new()),
+	];
+
+	sets[1].dyn_bit_set(100);
+	sets[2].dyn_bit_set(99);
+	sets[2].dyn_bit_set(5);
+	sets[2].dyn_bit_reset(5);
+
+	assert_eq!(sets.iter().map(|set| set.dyn_bit_count()).sum::<usize>(), 5);
+	assert_eq!(sets.iter().map(|set| set.dyn_bit_len()).sum::<usize>(), 16 + 128 + 100);
+	assert!(sets.iter().all(|set| set.dyn_bit_any() && !set.dyn_bit_all()));
+	assert!(sets[2].dyn_bit_test(99));
+}
@@ -0,0 +1,159 @@
+use std::vec::Vec;
+use super::BitSet;
+
+// Bits per block, the block counts are relative to their superblock
+const BLOCK: usize = 64;
+// Blocks per superblock
+const BLOCKS_PER_SUPER: usize = 8;
+const SUPER: usize = BLOCK * BLOCKS_PER_SUPER;
+
+/// Rank and select directory over a bitset.
+///
+/// Returned by `BitSet::bit_rank_select`.
+/// Stores the number of set bits before every 512 bit superblock and every 64 bit block within it,
+/// so the queries only need to scan the bits of a single block.
+///
+/// ```
+/// use bitset_core::BitSet;
+///
+/// let bits = [0x8000_0001u32, 0x0000_0010];
+/// let rs = bits.bit_rank_select();
+/// assert_eq!(rs.rank(32), 2);
+/// assert_eq!(rs.select(2), Some(36));
+/// assert_eq!(rs.select(3), None);
+/// ```
+pub struct RankSelect<'a, T: ?Sized> {
+	bits: &'a T,
+	supers: Vec<usize>,
+	blocks: Vec<u16>,
+}
+
+impl<'a, T: ?Sized> Clone for RankSelect<'a, T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		RankSelect { bits: self.bits, supers: self.supers.clone(), blocks: self.blocks.clone() }
+	}
+}
+
+impl<'a, T: ?Sized + BitSet> RankSelect<'a, T> {
+	/// Builds the directory for the bitset.
+	pub fn new(bits: &'a T) -> RankSelect<'a, T> {
+		let len = bits.bit_len();
+		let mut supers = Vec::new();
+		let mut blocks = Vec::new();
+		let mut total = 0;
+		let mut relative = 0;
+		for i in 0..len {
+			if i & (SUPER - 1) == 0 {
+				supers.push(total);
+				relative = 0;
+			}
+			if i & (BLOCK - 1) == 0 {
+				blocks.push(relative as u16);
+			}
+			let bit = bits.bit_test(i) as usize;
+			total += bit;
+			relative += bit;
+		}
+		supers.push(total);
+		RankSelect { bits, supers, blocks }
+	}
+
+	/// Returns the bitset.
+	#[inline]
+	pub fn bits(&self) -> &'a T {
+		self.bits
+	}
+
+	/// Returns the number of set bits.
+	#[inline]
+	pub fn count(&self) -> usize {
+		self.supers[self.supers.len() - 1]
+	}
+
+	/// Returns the number of set bits before the given bit.
+	///
+	/// Panics if `bit > bit_len()`.
+	pub fn rank(&self, bit: usize) -> usize {
+		let len = self.bits.bit_len();
+		assert!(bit <= len);
+		if bit == len {
+			return self.count();
+		}
+		let block = bit / BLOCK;
+		let start = block * BLOCK;
+		let mut rank = self.supers[bit / SUPER] + self.blocks[block] as usize;
+		for i in start..bit {
+			rank += self.bits.bit_test(i) as usize;
+		}
+		rank
+	}
+
+	/// Returns the index of the set bit with the given rank, ie. the `k + 1`th set bit.
+	///
+	/// Returns `None` if there are not more than `k` set bits.
+	pub fn select(&self, k: usize) -> Option<usize> {
+		if k >= self.count() {
+			return None;
+		}
+		// Last superblock starting with at most k set bits before it
+		let sup = self.supers.partition_point(|&rank| rank <= k) - 1;
+		let first = sup * BLOCKS_PER_SUPER;
+		let last = usize::min(first + BLOCKS_PER_SUPER, self.blocks.len());
+		let mut block = first;
+		while block + 1 < last && self.supers[sup] + self.blocks[block + 1] as usize <= k {
+			block += 1;
+		}
+		let mut rank = self.supers[sup] + self.blocks[block] as usize;
+		for i in block * BLOCK..self.bits.bit_len() {
+			if self.bits.bit_test(i) {
+				if rank == k {
+					return Some(i);
+				}
+				rank += 1;
+			}
+		}
+		None
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn tests() {
+	let mut random = super::xorshift(0x9e37_79b9);
+
+	// 1 Mbit with a mix of dense and sparse regions
+	let mut bits = vec![0u64; 1 << 14];
+	for (i, word) in bits.iter_mut().enumerate() {
+		let a = random() as u64;
+		let b = random() as u64;
+		*word = if i & 0x100 != 0 { a << 32 | b } else { a & b & (random() as u64) << 3 };
+	}
+	let rs = bits.bit_rank_select();
+
+	let mut prefix = vec![0usize; bits.bit_len() + 1];
+	let mut ones = Vec::new();
+	for i in 0..bits.bit_len() {
+		prefix[i + 1] = prefix[i] + bits.bit_test(i) as usize;
+		if bits.bit_test(i) {
+			ones.push(i);
+		}
+	}
+	assert_eq!(rs.count(), ones.len());
+	assert_eq!(rs.rank(bits.bit_len()), ones.len());
+
+	for _ in 0..2000 {
+		let bit = random() as usize % (bits.bit_len() + 1);
+		assert_eq!(rs.rank(bit), prefix[bit]);
+		let k = random() as usize % (ones.len() + 10);
+		assert_eq!(rs.select(k), ones.get(k).cloned());
+	}
+	assert_eq!(rs.select(0), ones.first().cloned());
+	assert_eq!(rs.select(ones.len() - 1), ones.last().cloned());
+
+	let empty = [0u8; 3];
+	let rs = empty.bit_rank_select();
+	assert_eq!(rs.rank(24), 0);
+	assert_eq!(rs.select(0), None);
+}
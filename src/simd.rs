@@ -1,7 +1,102 @@
-use super::BitSet;
+use core::{cmp, mem, ops, ptr, slice};
+use super::slice::{vote, Word, CHUNK_WORDS};
+use super::{BitOp, BitSet, BitSummary, Endian, LengthError};
+
+/// Extends the BitSet API with methods specific to simd-like containers.
+pub trait SimdBitSet: BitSet {
+	/// Number of lanes in each simd element.
+	const LANES: usize;
+	/// Array with a value for every lane.
+	type Lanes;
+	/// Type of the lanes.
+	type Word;
+
+	/// Counts the number of set bits in each lane.
+	fn bit_count_lanes(&self) -> Self::Lanes;
+	/// Returns the misalignment in bytes of the container relative to its simd width.
+	fn bit_align_offset(&self) -> usize;
+	/// Returns the lanes as a flat slice of words holding the same bits.
+	///
+	/// The nested arrays are contiguous without padding, so `bit X` of the container is `bit X` of the flat slice.
+	///
+	/// ```
+	/// use bitset_core::{BitSet, SimdBitSet};
+	///
+	/// let bits = [[0x1u32, 0, 0, 0x8000_0000]; 2];
+	/// let flat: &[u32] = bits.bit_as_flat();
+	/// assert_eq!(flat.len(), 8);
+	/// assert!(flat.bit_test(127) && flat.bit_test(128));
+	/// ```
+	fn bit_as_flat(&self) -> &[Self::Word];
+	/// Returns the lanes as a mutable flat slice of words holding the same bits.
+	fn bit_as_flat_mut(&mut self) -> &mut [Self::Word];
+}
+
+// The simd-like containers have the same bit layout as a slice of their lanes
+#[inline]
+fn flat<T, const N: usize>(this: &[[T; N]]) -> &[T] {
+	unsafe { slice::from_raw_parts(this.as_ptr() as *const T, this.len() * N) }
+}
+#[inline]
+fn flat_mut<T, const N: usize>(this: &mut [[T; N]]) -> &mut [T] {
+	unsafe { slice::from_raw_parts_mut(this.as_mut_ptr() as *mut T, this.len() * N) }
+}
+
+// Carry-save adder of three words per lane, returns the carries and the sums
+#[inline]
+fn csa<W: Word, const N: usize>(a: [W; N], b: [W; N], c: [W; N]) -> ([W; N], [W; N]) {
+	let mut high = [W::ZERO; N];
+	let mut low = [W::ZERO; N];
+	for i in 0..N {
+		let u = a[i] ^ b[i];
+		high[i] = a[i] & b[i] | u & c[i];
+		low[i] = u ^ c[i];
+	}
+	(high, low)
+}
+
+#[inline]
+fn count_lanes<W: Word, const N: usize>(x: [W; N]) -> usize {
+	x.iter().map(|&lane| lane.count_ones() as usize).sum()
+}
+
+// Number of elements below which the popcount of every element is cheaper than the adder tree
+const HARLEY_SEAL_MIN: usize = 64;
+
+// Harley-Seal population count, a carry-save adder tree reduces every 16 elements to a single popcount
+fn count_harley_seal<W: Word, const N: usize>(words: &[[W; N]]) -> usize {
+	let zero = [W::ZERO; N];
+	let (mut ones, mut twos, mut fours, mut eights) = (zero, zero, zero, zero);
+	let mut sixteens = 0;
+	let mut chunks = words.chunks_exact(16);
+	for chunk in &mut chunks {
+		let (twos_a, x) = csa(ones, chunk[0], chunk[1]);
+		let (twos_b, x) = csa(x, chunk[2], chunk[3]);
+		let (fours_a, y) = csa(twos, twos_a, twos_b);
+		let (twos_a, x) = csa(x, chunk[4], chunk[5]);
+		let (twos_b, x) = csa(x, chunk[6], chunk[7]);
+		let (fours_b, y) = csa(y, twos_a, twos_b);
+		let (eights_a, z) = csa(fours, fours_a, fours_b);
+		let (twos_a, x) = csa(x, chunk[8], chunk[9]);
+		let (twos_b, x) = csa(x, chunk[10], chunk[11]);
+		let (fours_a, y) = csa(y, twos_a, twos_b);
+		let (twos_a, x) = csa(x, chunk[12], chunk[13]);
+		let (twos_b, x) = csa(x, chunk[14], chunk[15]);
+		let (fours_b, y) = csa(y, twos_a, twos_b);
+		let (eights_b, z) = csa(z, fours_a, fours_b);
+		let (carry, w) = csa(eights, eights_a, eights_b);
+		ones = x;
+		twos = y;
+		fours = z;
+		eights = w;
+		sixteens += count_lanes(carry);
+	}
+	let rest: usize = chunks.remainder().iter().map(|&word| count_lanes(word)).sum();
+	16 * sixteens + 8 * count_lanes(eights) + 4 * count_lanes(fours) + 2 * count_lanes(twos) + count_lanes(ones) + rest
+}
 
 // Implement SIMD version by relying on very optimizer friendly code
-// Alignment is ignored but cam be taken care of by user code
+// Alignment is ignored but can be taken care of by user code, eg. with the Aligned wrappers
 macro_rules! impl_bit_set_simd {
 	([[$elem_ty:ident; $elem_len:literal]], $bits_per_word:literal, [$($idx:tt)*]) => {
 		impl BitSet for [[$elem_ty; $elem_len]] {
@@ -11,10 +106,7 @@ macro_rules! impl_bit_set_simd {
 			}
 			#[inline]
 			fn bit_init(&mut self, value: bool) -> &mut Self {
-				let value = [$elem_ty::wrapping_add(!(value as $elem_ty), 1); $elem_len];
-				for i in 0..self.len() {
-					self[i] = value;
-				}
+				self.fill([$elem_ty::wrapping_add(!(value as $elem_ty), 1); $elem_len]);
 				self
 			}
 			#[inline]
@@ -57,6 +149,54 @@ macro_rules! impl_bit_set_simd {
 				self
 			}
 			#[inline]
+			fn bit_test_and_set(&mut self, bit: usize) -> bool {
+				let index = bit / $bits_per_word;
+				let lane = (bit / ($bits_per_word / $elem_len)) % $elem_len;
+				let mask = 1 << bit % ($bits_per_word / $elem_len);
+				let old = self[index][lane] & mask != 0;
+				self[index][lane] |= mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+				let index = bit / $bits_per_word;
+				let lane = (bit / ($bits_per_word / $elem_len)) % $elem_len;
+				let mask = 1 << bit % ($bits_per_word / $elem_len);
+				let old = self[index][lane] & mask != 0;
+				self[index][lane] &= !mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+				let index = bit / $bits_per_word;
+				let lane = (bit / ($bits_per_word / $elem_len)) % $elem_len;
+				let mask = 1 << bit % ($bits_per_word / $elem_len);
+				let old = self[index][lane] & mask != 0;
+				self[index][lane] ^= mask;
+				old
+			}
+			#[inline]
+			fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+				flat_mut(self).bit_cond_range(range, value);
+				self
+			}
+			#[inline]
+			fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+				flat_mut(self).bit_set_bits(indices);
+				self
+			}
+			#[inline]
+			fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+				debug_assert!(bit_in_word < $bits_per_word / $elem_len, "bit index out of range");
+				flat(self).bit_test_at(word, bit_in_word)
+			}
+			#[inline]
+			fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+				debug_assert!(bit_in_word < $bits_per_word / $elem_len, "bit index out of range");
+				flat_mut(self).bit_set_at(word, bit_in_word);
+				self
+			}
+			#[inline]
 			fn bit_all(&self) -> bool {
 				let mut result = [!0; $elem_len];
 				for i in 0..self.len() {
@@ -73,7 +213,26 @@ macro_rules! impl_bit_set_simd {
 				result != [0; $elem_len]
 			}
 			#[inline]
+			fn bit_is_empty(&self) -> bool {
+				self.chunks(CHUNK_WORDS).all(|chunk| !chunk.bit_any())
+			}
+			#[inline]
+			fn bit_is_full(&self) -> bool {
+				self.chunks(CHUNK_WORDS).all(|chunk| chunk.bit_all())
+			}
+			#[inline]
+			fn bit_all_len(&self, len: usize) -> bool {
+				flat(self).bit_all_len(len)
+			}
+			#[inline]
+			fn bit_any_len(&self, len: usize) -> bool {
+				flat(self).bit_any_len(len)
+			}
+			#[inline]
 			fn bit_eq(&self, rhs: &Self) -> bool {
+				if ptr::eq(self, rhs) {
+					return true;
+				}
 				let mut result = true;
 				for i in 0..self.len() {
 					result &= self[i] == rhs[i];
@@ -89,14 +248,44 @@ macro_rules! impl_bit_set_simd {
 				result
 			}
 			#[inline]
+			fn bit_disjoint_range(&self, rhs: &Self, range: ops::Range<usize>) -> bool {
+				flat(self).bit_disjoint_range(flat(rhs), range)
+			}
+			#[inline]
 			fn bit_subset(&self, rhs: &Self) -> bool {
 				let mut result = true;
 				for i in 0..self.len() {
-					result &= [$(self[i][$idx] & rhs[i][$idx]),*] == rhs[i];
+					result &= [$(self[i][$idx] & rhs[i][$idx]),*] == self[i];
 				}
 				result
 			}
 			#[inline]
+			fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, mask.len());
+				let mut result = [0; $elem_len];
+				for i in 0..len {
+					$(result[$idx] |= (self[i][$idx] ^ rhs[i][$idx]) & mask[i][$idx];)*
+				}
+				result == [0; $elem_len]
+			}
+			#[inline]
+			fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, mask.len());
+				let mut result = [0; $elem_len];
+				for i in 0..len {
+					$(result[$idx] |= self[i][$idx] & !rhs[i][$idx] & mask[i][$idx];)*
+				}
+				result == [0; $elem_len]
+			}
+			#[inline]
+			fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+				flat(self).bit_missing_from(flat(available), flat_mut(out))
+			}
+			#[inline]
 			fn bit_or(&mut self, rhs: &Self) -> &mut Self {
 				assert_eq!(self.len(), rhs.len());
 				for i in 0..self.len() {
@@ -121,6 +310,29 @@ macro_rules! impl_bit_set_simd {
 				self
 			}
 			#[inline]
+			fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+				flat_mut(self).bit_andnot_range(flat(rhs), range);
+				self
+			}
+			#[inline]
+			fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+				assert_eq!(self.len(), rhs.len());
+				let mut changed = [0; $elem_len];
+				for i in 0..self.len() {
+					$(changed[$idx] |= self[i][$idx] & rhs[i][$idx];)*
+					$(self[i][$idx] &= !rhs[i][$idx];)*
+				}
+				changed != [0; $elem_len]
+			}
+			#[inline]
+			fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+				flat_mut(self).bit_or_delta(flat(rhs))
+			}
+			#[inline]
+			fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+				flat_mut(self).bit_andnot_delta(flat(rhs))
+			}
+			#[inline]
 			fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
 				assert_eq!(self.len(), rhs.len());
 				for i in 0..self.len() {
@@ -136,6 +348,11 @@ macro_rules! impl_bit_set_simd {
 				self
 			}
 			#[inline]
+			fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+				flat_mut(self).bit_not_cond(condition);
+				self
+			}
+			#[inline]
 			fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
 				let len = self.len();
 				assert_eq!(len, rhs.len());
@@ -146,13 +363,259 @@ macro_rules! impl_bit_set_simd {
 				self
 			}
 			#[inline]
+			fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+				flat_mut(self).bit_mask_inv(flat(rhs), flat(mask));
+				self
+			}
+			#[inline]
+			fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+				flat_mut(self).bit_or_and(flat(rhs), flat(cond));
+				self
+			}
+			#[inline]
+			fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+				let len = self.len();
+				assert_eq!(len, a.len());
+				assert_eq!(len, b.len());
+				assert_eq!(len, mask.len());
+				for i in 0..len {
+					$(self[i][$idx] = a[i][$idx] & !mask[i][$idx] | b[i][$idx] & mask[i][$idx];)*
+				}
+				self
+			}
+			#[inline]
+			fn bit_vote_into(&mut self, inputs: &[&Self], threshold: usize) -> &mut Self {
+				for input in inputs {
+					assert_eq!(self.len(), input.len());
+				}
+				for i in 0..self.len() {
+					$(self[i][$idx] = vote(inputs.iter().map(|input| input[i][$idx]), inputs.len(), threshold);)*
+				}
+				self
+			}
+			#[inline]
+			fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					$(self[i][$idx] |= word;)*
+				}
+				self
+			}
+			#[inline]
+			fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					$(self[i][$idx] &= word;)*
+				}
+				self
+			}
+			#[inline]
+			fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					$(self[i][$idx] ^= word;)*
+				}
+				self
+			}
+			#[inline]
+			fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+				flat_mut(self).bit_insert(index, value);
+				self
+			}
+			#[inline]
+			fn bit_remove(&mut self, index: usize) -> bool {
+				flat_mut(self).bit_remove(index)
+			}
+			#[inline]
+			fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+				flat_mut(self).bit_shl_len(n, len);
+				self
+			}
+			#[inline]
+			fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				flat_mut(self).bit_shl_fill(n, fill);
+				self
+			}
+			#[inline]
+			fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				flat_mut(self).bit_shr_fill(n, fill);
+				self
+			}
+			#[inline]
+			fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+				flat_mut(self).bit_reverse_range(range);
+				self
+			}
+			#[inline]
+			fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+				flat_mut(self).bit_reverse_groups(group_bits);
+				self
+			}
+			#[inline]
+			fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+				flat_mut(self).bit_swap_ranges(a, b, len);
+				self
+			}
+			#[inline]
+			fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+				let len = cmp::max(a.len(), b.len());
+				assert_eq!(self.len(), len);
+				for i in 0..len {
+					let x = if i < a.len() { a[i] } else { [0; $elem_len] };
+					let y = if i < b.len() { b[i] } else { [0; $elem_len] };
+					match op {
+						BitOp::Or => { $(self[i][$idx] = x[$idx] | y[$idx];)* },
+						BitOp::And => { $(self[i][$idx] = x[$idx] & y[$idx];)* },
+						BitOp::AndNot => { $(self[i][$idx] = x[$idx] & !y[$idx];)* },
+						BitOp::Xor => { $(self[i][$idx] = x[$idx] ^ y[$idx];)* },
+					}
+				}
+				self
+			}
+			#[inline]
+			fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+				flat_mut(self).bit_and_tiled(flat(pattern));
+				self
+			}
+			#[inline]
+			fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+				flat_mut(self).bit_or_tiled(flat(pattern));
+				self
+			}
+			#[inline]
+			fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+				flat_mut(self).bit_xor_tiled(flat(pattern));
+				self
+			}
+			#[inline]
+			fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				flat_mut(self).bit_or_tiled(flat(rhs));
+				self
+			}
+			#[inline]
+			fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				flat_mut(self).bit_and_tiled(flat(rhs));
+				self
+			}
+			#[inline]
+			fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				flat_mut(self).bit_xor_tiled(flat(rhs));
+				self
+			}
+			#[inline]
 			fn bit_count(&self) -> usize {
+				if self.len() >= HARLEY_SEAL_MIN {
+					return count_harley_seal(self);
+				}
 				let mut result = 0;
 				for i in 0..self.len() {
 					$(result += self[i][$idx].count_ones() as usize;)*
 				}
 				result
 			}
+			#[inline]
+			fn bit_count_up_to(&self, limit: usize) -> usize {
+				flat(self).bit_count_up_to(limit)
+			}
+			#[inline]
+			fn bit_andnot_count(&self, rhs: &Self) -> usize {
+				flat(self).bit_andnot_count(flat(rhs))
+			}
+			#[inline]
+			fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+				flat(self).bit_and_count_at_least(flat(rhs), threshold)
+			}
+			#[inline]
+			fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+				flat(self).bit_hamming_at_most(flat(rhs), max)
+			}
+			#[inline]
+			fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+				// Only visits the bits in the intersection of the lanes
+				flat(self).bit_weighted_overlap(flat(rhs), weights)
+			}
+			#[inline]
+			fn bit_leading_ones(&self) -> usize {
+				flat(self).bit_leading_ones()
+			}
+			#[inline]
+			fn bit_trailing_ones(&self) -> usize {
+				flat(self).bit_trailing_ones()
+			}
+			#[inline]
+			fn bit_find_first_zero(&self) -> Option<usize> {
+				flat(self).bit_find_first_zero()
+			}
+			#[inline]
+			fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+				flat(self).bit_find_next_zero(from)
+			}
+			#[inline]
+			fn bit_select_high(&self, k: usize) -> Option<usize> {
+				flat(self).bit_select_high(k)
+			}
+			#[inline]
+			fn bit_summary(&self) -> BitSummary {
+				flat(self).bit_summary()
+			}
+			#[inline]
+			fn bit_rank_table(&self, out: &mut [usize]) {
+				flat(self).bit_rank_table(out)
+			}
+			#[inline]
+			fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+				flat_mut(self).bit_load_bytes(bytes, endian)
+			}
+			#[inline]
+			fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+				flat(self).bit_store_bytes(out, endian)
+			}
+			#[inline]
+			fn bit_nonzero_words(&self) -> usize {
+				let mut result = 0;
+				for i in 0..self.len() {
+					$(result += (self[i][$idx] != 0) as usize;)*
+				}
+				result
+			}
+			#[inline]
+			fn bit_first_nonzero_word(&self) -> Option<usize> {
+				let index = self.iter().position(|word| *word != [0; $elem_len])?;
+				self[index].iter().position(|&lane| lane != 0).map(|lane| index * $elem_len + lane)
+			}
+			#[inline]
+			fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+				flat(self).bit_fold_words(init, f)
+			}
+		}
+		impl SimdBitSet for [[$elem_ty; $elem_len]] {
+			const LANES: usize = $elem_len;
+			type Lanes = [usize; $elem_len];
+			type Word = $elem_ty;
+
+			#[inline]
+			fn bit_count_lanes(&self) -> [usize; $elem_len] {
+				let mut result = [0; $elem_len];
+				for i in 0..self.len() {
+					$(result[$idx] += self[i][$idx].count_ones() as usize;)*
+				}
+				result
+			}
+			#[inline]
+			fn bit_align_offset(&self) -> usize {
+				self.as_ptr() as usize % mem::size_of::<[$elem_ty; $elem_len]>()
+			}
+			#[inline]
+			fn bit_as_flat(&self) -> &[$elem_ty] {
+				flat(self)
+			}
+			#[inline]
+			fn bit_as_flat_mut(&mut self) -> &mut [$elem_ty] {
+				flat_mut(self)
+			}
 		}
 	};
 }
@@ -168,6 +631,10 @@ impl_bit_set_simd!([[ u8; 32]], 256, [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 1
 impl_bit_set_simd!([[u16; 16]], 256, [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15]);
 impl_bit_set_simd!([[u32;  8]], 256, [0 1 2 3 4 5 6 7]);
 impl_bit_set_simd!([[u64;  4]], 256, [0 1 2 3]);
+impl_bit_set_simd!([[u128; 2]], 256, [0 1]);
+
+// simd512
+impl_bit_set_simd!([[u128; 4]], 512, [0 1 2 3]);
 
 //----------------------------------------------------------------
 
@@ -190,9 +657,165 @@ fn tests256() {
 	let mut words = [[0u16; 16]; 16];
 	let mut dwords = [[0u32; 8]; 8];
 	let mut qwords = [[0u64; 4]; 4];
+	let mut owords = [[0u128; 2]; 2];
 
 	super::unary_tests(&mut bytes[..]);
 	super::unary_tests(&mut words[..]);
 	super::unary_tests(&mut dwords[..]);
 	super::unary_tests(&mut qwords[..]);
+	super::unary_tests(&mut owords[..]);
+}
+
+#[test]
+fn tests512() {
+	let mut owords = [[0u128; 4]; 2];
+	super::unary_tests(&mut owords[..]);
+
+	// Same bit layout as the narrower lanes
+	let mut qwords = [[0u64; 4]; 4];
+	owords.bit_init(false);
+	for &bit in &[0, 63, 64, 127, 128, 300, 511, 512, 1023] {
+		owords.bit_set(bit);
+		qwords.bit_set(bit);
+	}
+	assert_eq!(owords.bit_len(), 1024);
+	assert_eq!(owords.bit_count(), 9);
+	assert_eq!(owords.bit_count_lanes(), [5, 1, 1, 2]);
+	assert_eq!(owords.bit_fold_words(0, |acc, _, word| acc ^ word), qwords.bit_fold_words(0, |acc, _, word| acc ^ word));
+	assert!((0..1024).all(|bit| owords.bit_test(bit) == qwords.bit_test(bit)));
+
+	let mut other = [[0u128; 4]; 2];
+	other.bit_init(true).bit_andnot(&owords);
+	assert_eq!(other.bit_count(), 1024 - 9);
+	assert!(other.bit_disjoint(&owords));
+	assert!(other.bit_not().bit_eq(&owords));
+}
+
+#[test]
+fn test_combine_into() {
+	let mut dst = [[0u32; 4]; 2];
+	dst.bit_combine_into(&[[1; 4]], &[[2; 4], [3; 4]], BitOp::Or);
+	assert_eq!(dst, [[3; 4], [3; 4]]);
+	dst.bit_combine_into(&[[1; 4]], &[[3; 4], [3; 4]], BitOp::And);
+	assert_eq!(dst, [[1; 4], [0; 4]]);
+}
+
+#[test]
+fn test_count_lanes() {
+	let bits = [[0x1u32, 0x3, 0x7, 0xf]; 3];
+	assert_eq!(bits.bit_count_lanes(), [3, 6, 9, 12]);
+	assert_eq!(<[[u32; 4]]>::LANES, 4);
+
+	let bits = [[0xffu8; 32]; 2];
+	assert_eq!(bits.bit_count_lanes(), [16; 32]);
+}
+
+#[test]
+fn test_nonzero_words() {
+	let lanes = [[0u32, 0, 0, 0], [0, 0, 7, 0], [1, 0, 0, 1]];
+	assert_eq!(lanes.bit_nonzero_words(), 3);
+	assert_eq!(lanes.bit_first_nonzero_word(), Some(6));
+	assert_eq!([[0u64; 2]; 2].bit_first_nonzero_word(), None);
+}
+
+#[test]
+fn test_cond_range() {
+	let mut bits = [[0u32; 4]; 2];
+	bits.bit_cond_range(30..130, true);
+	assert_eq!(bits, [[0xc000_0000, !0, !0, !0], [0x3, 0, 0, 0]]);
+	bits.bit_cond_range(31..129, false);
+	assert_eq!(bits, [[0x4000_0000, 0, 0, 0], [0x2, 0, 0, 0]]);
+}
+
+#[test]
+fn test_broadcast() {
+	let mut bits = [[0u16; 8]; 2];
+	bits.bit_or_broadcast(0x1_0001);
+	assert_eq!(bits, [[1; 8]; 2]);
+	bits.bit_xor_broadcast(3);
+	assert_eq!(bits, [[2; 8]; 2]);
+	bits.bit_and_broadcast(1);
+	assert!(bits.bit_none());
+}
+
+#[test]
+fn test_ones() {
+	let bits = [[!0u32, !0, !0, !0], [!0, 0x1, 0, 0xc000_0000]];
+	assert_eq!(bits.bit_trailing_ones(), 161);
+	assert_eq!(bits.bit_leading_ones(), 2);
+}
+
+#[test]
+fn test_blend_into() {
+	let mut out = [[0u64; 2]; 2];
+	out.bit_blend_into(&[[0x0f; 2]; 2], &[[0xf0; 2]; 2], &[[0x3c, 0], [!0, 0xff]]);
+	assert_eq!(out, [[0x33, 0x0f], [0xf0, 0xf0]]);
+}
+
+#[test]
+fn test_is_empty_full() {
+	let mut bits = [[0u8; 16]; 4];
+	assert!(bits.bit_is_empty() && !bits.bit_is_full());
+	bits.bit_set(300);
+	assert!(!bits.bit_is_empty() && !bits.bit_is_full());
+	bits.bit_init(true);
+	assert!(bits.bit_is_full());
+}
+
+#[test]
+fn test_subset() {
+	let a = [[0x01u32, 0, 0x10, 0]; 2];
+	let b = [[0x03u32, 0, 0x10, 0x80]; 2];
+	assert!(a.bit_subset(&b));
+	assert!(!b.bit_subset(&a));
+	assert_ne!(a.bit_subset(&b), b.bit_subset(&a));
+	assert!(b.bit_superset(&a));
+	assert!(a.bit_subset(&a));
+}
+
+#[test]
+fn test_masked() {
+	let a = [[0x0fu32, 0xf0, 0, 0]; 2];
+	let b = [[0x0eu32, 0xf0, 1, 0]; 2];
+	assert!(a.bit_eq_masked(&b, &[[0xfe, !0, 0, !0]; 2]));
+	assert!(!a.bit_eq_masked(&b, &[[0xff, !0, 0, !0]; 2]));
+	assert!(b.bit_subset_masked(&a, &[[!0, !0, 0, !0]; 2]));
+	assert!(!b.bit_subset_masked(&a, &[[0, 0, 1, 0]; 2]));
+}
+
+#[test]
+fn test_as_flat() {
+	let mut dwords = [[0u32; 4]; 16];
+	for (i, word) in dwords.bit_as_flat_mut().iter_mut().enumerate() {
+		*word = (i as u32).wrapping_mul(0x9e37_79b9);
+	}
+	let flat = dwords.bit_as_flat();
+	assert_eq!(flat.len(), 64);
+	assert_eq!(flat.bit_count(), dwords.bit_count());
+	assert!((0..dwords.bit_len()).all(|i| flat.bit_test(i) == dwords.bit_test(i)));
+
+	let mut plain = [0u32; 64];
+	plain.copy_from_slice(flat);
+	assert!(plain.bit_eq(flat));
+	let mut qwords = [[0u64; 4]; 2];
+	qwords.bit_as_flat_mut().bit_set(300);
+	assert!(qwords.bit_test(300));
+	assert_eq!(qwords.bit_as_flat().bit_count(), 1);
+}
+
+#[test]
+fn test_count_harley_seal() {
+	let mut random = super::xorshift(0x9e37_79b9);
+	let mut qwords = [[0u64; 4]; 1024];
+	for word in qwords.bit_as_flat_mut() {
+		let seed = random();
+		*word = (seed as u64) << 32 | seed.rotate_left(7) as u64;
+	}
+	let simple = |bits: &[[u64; 4]]| bits.iter().flatten().map(|word| word.count_ones() as usize).sum::<usize>();
+	for &len in &[0, 15, 16, 17, 63, 64, 65, 100, 1024] {
+		assert_eq!(qwords[..len].bit_count(), simple(&qwords[..len]));
+		assert_eq!(count_harley_seal(&qwords[..len]), simple(&qwords[..len]));
+	}
+	assert_eq!([[!0u8; 32]; 200].bit_count(), 200 * 256);
+	assert_eq!(count_harley_seal(&[[0xffffu16; 8]; 33]), 33 * 128);
 }
@@ -1,4 +1,199 @@
-use super::BitSet;
+use core::{cmp, ops, ptr};
+use core::convert::TryInto;
+use super::{BitOp, BitSet, BitSummary, Endian, LengthError};
+
+// Word level helpers shared by the slice implementations
+pub(crate) trait Word: Copy + Eq + ops::Not<Output = Self> + ops::BitAnd<Output = Self> + ops::BitOr<Output = Self> + ops::BitXor<Output = Self> + ops::Shl<usize, Output = Self> + ops::Shr<usize, Output = Self> {
+	const BITS: usize;
+	const ZERO: Self;
+	const ONES: Self;
+	fn count_ones(self) -> u32;
+}
+macro_rules! impl_word {
+	($ty:ty) => {
+		impl_word!($ty, <$ty>::count_ones);
+	};
+	($ty:ty, $count_ones:expr) => {
+		impl Word for $ty {
+			const BITS: usize = <$ty>::BITS as usize;
+			const ZERO: $ty = 0;
+			const ONES: $ty = !0;
+			#[inline]
+			fn count_ones(self) -> u32 {
+				($count_ones)(self)
+			}
+		}
+	};
+}
+#[cfg(not(feature = "lut-popcount"))]
+impl_word!(u8);
+#[cfg(not(feature = "lut-popcount"))]
+impl_word!(u16);
+#[cfg(feature = "lut-popcount")]
+impl_word!(u8, lut_count_u8);
+#[cfg(feature = "lut-popcount")]
+impl_word!(u16, lut_count_u16);
+impl_word!(u32);
+impl_word!(u64);
+impl_word!(u128);
+
+// Population count of every byte value for targets without a popcount instruction
+#[cfg(feature = "lut-popcount")]
+static POPCOUNT: [u8; 256] = {
+	let mut table = [0u8; 256];
+	let mut i = 1;
+	while i < 256 {
+		table[i] = table[i >> 1] + (i & 1) as u8;
+		i += 1;
+	}
+	table
+};
+#[cfg(feature = "lut-popcount")]
+#[inline]
+fn lut_count_u8(word: u8) -> u32 {
+	POPCOUNT[word as usize] as u32
+}
+#[cfg(feature = "lut-popcount")]
+#[inline]
+fn lut_count_u16(word: u16) -> u32 {
+	POPCOUNT[(word & 0xff) as usize] as u32 + POPCOUNT[(word >> 8) as usize] as u32
+}
+
+// Mask with the low n bits set, where 0 < n <= W::BITS
+#[inline]
+fn low_mask<W: Word>(n: usize) -> W {
+	W::ONES >> (W::BITS - n)
+}
+
+// Reads n bits starting at the given bit, where 0 < n <= W::BITS
+#[inline]
+fn extract<W: Word>(words: &[W], bit: usize, n: usize) -> W {
+	let index = bit / W::BITS;
+	let shift = bit % W::BITS;
+	let mut value = words[index] >> shift;
+	if shift + n > W::BITS {
+		value = value | words[index + 1] << (W::BITS - shift);
+	}
+	value & low_mask(n)
+}
+
+// Writes n bits starting at the given bit, where 0 < n <= W::BITS
+#[inline]
+fn deposit<W: Word>(words: &mut [W], bit: usize, n: usize, value: W) {
+	let index = bit / W::BITS;
+	let shift = bit % W::BITS;
+	let mask = low_mask::<W>(n);
+	let value = value & mask;
+	words[index] = words[index] & !(mask << shift) | value << shift;
+	if shift + n > W::BITS {
+		let rest = W::BITS - shift;
+		words[index + 1] = words[index + 1] & !(mask >> rest) | value >> rest;
+	}
+}
+
+// Applies f to every word, the u64 aligned middle is processed as u64 with g instead
+#[inline]
+fn map_wide<W: Copy>(words: &mut [W], f: fn(W) -> W, g: fn(u64) -> u64) {
+	// Any bit pattern is a valid unsigned integer
+	let (head, mid, tail) = unsafe { words.align_to_mut::<u64>() };
+	for word in head.iter_mut().chain(tail) {
+		*word = f(*word);
+	}
+	for word in mid {
+		*word = g(*word);
+	}
+}
+
+// Combines every word of dst with src using f, where both are aligned alike the u64 middle is combined with g instead
+// The wide path requires every part to line up, anything else falls back to combining word by word
+#[inline]
+fn zip_wide<W: Copy>(dst: &mut [W], src: &[W], f: fn(W, W) -> W, g: fn(u64, u64) -> u64) {
+	let same_len = dst.len() == src.len();
+	// Any bit pattern is a valid unsigned integer
+	let (dst_head, dst_mid, dst_tail) = unsafe { dst.align_to_mut::<u64>() };
+	let (src_head, src_mid, src_tail) = unsafe { src.align_to::<u64>() };
+	if same_len && dst_head.len() == src_head.len() && dst_mid.len() == src_mid.len() {
+		zip(dst_head, src_head, f);
+		zip(dst_mid, src_mid, g);
+		zip(dst_tail, src_tail, f);
+	}
+	else {
+		zip(dst, src, f);
+	}
+}
+
+#[inline]
+fn zip<W: Copy>(dst: &mut [W], src: &[W], f: fn(W, W) -> W) {
+	for (x, &y) in dst.iter_mut().zip(src) {
+		*x = f(*x, y);
+	}
+}
+
+// Sums the population counts of the words, visiting no more words once the limit is reached
+#[inline]
+fn count_up_to<I: Iterator<Item = u32>>(mut counts: I, limit: usize) -> usize {
+	let mut result = 0;
+	while result < limit {
+		match counts.next() {
+			Some(count) => result += count as usize,
+			None => break,
+		}
+	}
+	cmp::min(result, limit)
+}
+
+// Returns if the population counts of the words sum to at most max, visiting no more words once it is exceeded
+#[inline]
+fn sum_at_most<I: Iterator<Item = u32>>(counts: I, max: usize) -> bool {
+	let mut result = 0;
+	for count in counts {
+		result += count as usize;
+		if result > max {
+			return false;
+		}
+	}
+	true
+}
+
+// Sets the bits present in at least threshold of the words
+// Counts every bit position at once with bit-sliced counters, one plane per bit of the count
+pub(crate) fn vote<W: Word, I: Iterator<Item = W>>(words: I, len: usize, threshold: usize) -> W {
+	if threshold == 0 {
+		return W::ONES;
+	}
+	if threshold > len {
+		return W::ZERO;
+	}
+	let planes_len = (usize::BITS - len.leading_zeros()) as usize;
+	let mut planes = [W::ZERO; usize::BITS as usize];
+	for word in words {
+		let mut carry = word;
+		for plane in planes[..planes_len].iter_mut() {
+			if carry == W::ZERO {
+				break;
+			}
+			let next = *plane & carry;
+			*plane = *plane ^ carry;
+			carry = next;
+		}
+	}
+	// Compare the counters against the threshold from the most significant plane down
+	let mut greater = W::ZERO;
+	let mut equal = W::ONES;
+	for (k, &plane) in planes[..planes_len].iter().enumerate().rev() {
+		if threshold >> k & 1 != 0 {
+			equal = equal & plane;
+		}
+		else {
+			greater = greater | equal & plane;
+			equal = equal & !plane;
+		}
+	}
+	greater | equal
+}
+
+// Number of words scanned between early exit checks
+pub(crate) const CHUNK_WORDS: usize = 8;
 
 macro_rules! impl_bit_set_slice {
 	([$elem_ty:ty], $bits_per_word:literal) => {
@@ -10,10 +205,8 @@ macro_rules! impl_bit_set_slice {
 
 			#[inline]
 			fn bit_init(&mut self, value: bool) -> &mut Self {
-				let value = <$elem_ty>::wrapping_add(!(value as $elem_ty), 1);
-				for i in 0..self.len() {
-					self[i] = value;
-				}
+				// Lowers to memset for the all zero and all ones patterns
+				self.fill(<$elem_ty>::wrapping_add(!(value as $elem_ty), 1));
 				self
 			}
 
@@ -43,6 +236,73 @@ macro_rules! impl_bit_set_slice {
 				self[index] = (self[index] & !mask) | (<$elem_ty>::wrapping_add(!(value as $elem_ty), 1) & mask);
 				self
 			}
+			#[inline]
+			fn bit_test_and_set(&mut self, bit: usize) -> bool {
+				let word = &mut self[bit / $bits_per_word];
+				let mask = 1 << bit % $bits_per_word;
+				let old = *word & mask != 0;
+				*word |= mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+				let word = &mut self[bit / $bits_per_word];
+				let mask = 1 << bit % $bits_per_word;
+				let old = *word & mask != 0;
+				*word &= !mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+				let word = &mut self[bit / $bits_per_word];
+				let mask = 1 << bit % $bits_per_word;
+				let old = *word & mask != 0;
+				*word ^= mask;
+				old
+			}
+			#[inline]
+			fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+				assert!(range.start <= range.end && range.end <= self.bit_len());
+				if range.start < range.end {
+					let value = <$elem_ty>::wrapping_add(!(value as $elem_ty), 1);
+					let first = range.start / $bits_per_word;
+					let last = (range.end - 1) / $bits_per_word;
+					let head = !0 << range.start % $bits_per_word;
+					let tail = !0 >> ($bits_per_word - 1 - (range.end - 1) % $bits_per_word);
+					for i in first..=last {
+						let mut mask: $elem_ty = !0;
+						if i == first { mask &= head; }
+						if i == last { mask &= tail; }
+						self[i] = (self[i] & !mask) | (value & mask);
+					}
+				}
+				self
+			}
+			#[inline]
+			fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+				debug_assert!(bit_in_word < $bits_per_word, "bit index out of range");
+				self[word] & (1 << bit_in_word) != 0
+			}
+			#[inline]
+			fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+				debug_assert!(bit_in_word < $bits_per_word, "bit index out of range");
+				self[word] |= 1 << bit_in_word;
+				self
+			}
+			#[inline]
+			fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+				let mut i = 0;
+				while i < indices.len() {
+					let index = indices[i] / $bits_per_word;
+					let mut word: $elem_ty = 0;
+					while i < indices.len() && indices[i] / $bits_per_word == index {
+						word |= 1 << indices[i] % $bits_per_word;
+						i += 1;
+					}
+					self[index] |= word;
+				}
+				self
+			}
 
 			#[inline]
 			fn bit_all(&self) -> bool {
@@ -60,9 +320,46 @@ macro_rules! impl_bit_set_slice {
 				}
 				result != 0
 			}
+			#[inline]
+			fn bit_is_empty(&self) -> bool {
+				// Branch once per chunk rather than once per word
+				self.chunks(CHUNK_WORDS).all(|chunk| !chunk.bit_any())
+			}
+			#[inline]
+			fn bit_is_full(&self) -> bool {
+				self.chunks(CHUNK_WORDS).all(|chunk| chunk.bit_all())
+			}
 
+			#[inline]
+			fn bit_all_len(&self, len: usize) -> bool {
+				assert!(len <= self.bit_len());
+				let full = len / $bits_per_word;
+				let rest = len % $bits_per_word;
+				let mut result = self[..full].bit_all();
+				if rest > 0 {
+					let mask = !0 >> ($bits_per_word - rest);
+					result &= self[full] & mask == mask;
+				}
+				result
+			}
+			#[inline]
+			fn bit_any_len(&self, len: usize) -> bool {
+				assert!(len <= self.bit_len());
+				let full = len / $bits_per_word;
+				let rest = len % $bits_per_word;
+				let mut result = self[..full].bit_any();
+				if rest > 0 {
+					let mask = !0 >> ($bits_per_word - rest);
+					result |= self[full] & mask != 0;
+				}
+				result
+			}
 			#[inline]
 			fn bit_eq(&self, rhs: &Self) -> bool {
+				// Only the comparisons can see the same set twice, the mutating operations never alias their operands
+				if ptr::eq(self, rhs) {
+					return true;
+				}
 				let mut result = true;
 				for i in 0..self.len() {
 					result &= self[i] == rhs[i];
@@ -78,28 +375,78 @@ macro_rules! impl_bit_set_slice {
 				result
 			}
 			#[inline]
+			fn bit_disjoint_range(&self, rhs: &Self, range: ops::Range<usize>) -> bool {
+				assert_eq!(self.len(), rhs.len());
+				assert!(range.start <= range.end && range.end <= self.bit_len());
+				if range.start < range.end {
+					let first = range.start / $bits_per_word;
+					let last = (range.end - 1) / $bits_per_word;
+					let head = !0 << range.start % $bits_per_word;
+					let tail = !0 >> ($bits_per_word - 1 - (range.end - 1) % $bits_per_word);
+					for i in first..=last {
+						let mut mask: $elem_ty = !0;
+						if i == first { mask &= head; }
+						if i == last { mask &= tail; }
+						if self[i] & rhs[i] & mask != 0 {
+							return false;
+						}
+					}
+				}
+				true
+			}
+			#[inline]
 			fn bit_subset(&self, rhs: &Self) -> bool {
+				if ptr::eq(self, rhs) {
+					return true;
+				}
 				let mut result = true;
 				for i in 0..self.len() {
 					result &= self[i] | rhs[i] == rhs[i];
 				}
 				result
 			}
+			#[inline]
+			fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, mask.len());
+				let mut result = 0;
+				for i in 0..len {
+					result |= (self[i] ^ rhs[i]) & mask[i];
+				}
+				result == 0
+			}
+			#[inline]
+			fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, mask.len());
+				let mut result = 0;
+				for i in 0..len {
+					result |= self[i] & !rhs[i] & mask[i];
+				}
+				result == 0
+			}
+			#[inline]
+			fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+				let len = self.len();
+				assert_eq!(len, available.len());
+				assert_eq!(len, out.len());
+				for i in 0..len {
+					out[i] = self[i] & !available[i];
+				}
+			}
 
 			#[inline]
 			fn bit_or(&mut self, rhs: &Self) -> &mut Self {
 				assert_eq!(self.len(), rhs.len());
-				for i in 0..self.len() {
-					self[i] |= rhs[i];
-				}
+				zip_wide(self, rhs, |x, y| x | y, |x, y| x | y);
 				self
 			}
 			#[inline]
 			fn bit_and(&mut self, rhs: &Self) -> &mut Self {
 				assert_eq!(self.len(), rhs.len());
-				for i in 0..self.len() {
-					self[i] &= rhs[i];
-				}
+				zip_wide(self, rhs, |x, y| x & y, |x, y| x & y);
 				self
 			}
 			#[inline]
@@ -111,17 +458,69 @@ macro_rules! impl_bit_set_slice {
 				self
 			}
 			#[inline]
-			fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+			fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+				assert_eq!(self.len(), rhs.len());
+				assert!(range.start <= range.end && range.end <= self.bit_len());
+				if range.start < range.end {
+					let first = range.start / $bits_per_word;
+					let last = (range.end - 1) / $bits_per_word;
+					let head = !0 << range.start % $bits_per_word;
+					let tail = !0 >> ($bits_per_word - 1 - (range.end - 1) % $bits_per_word);
+					for i in first..=last {
+						let mut mask: $elem_ty = !0;
+						if i == first { mask &= head; }
+						if i == last { mask &= tail; }
+						self[i] &= !(rhs[i] & mask);
+					}
+				}
+				self
+			}
+			#[inline]
+			fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+				assert_eq!(self.len(), rhs.len());
+				let mut changed = 0;
+				for i in 0..self.len() {
+					changed |= self[i] & rhs[i];
+					self[i] &= !rhs[i];
+				}
+				changed != 0
+			}
+			#[inline]
+			fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+				assert_eq!(self.len(), rhs.len());
+				let mut delta = 0;
+				for i in 0..self.len() {
+					delta += (!self[i] & rhs[i]).count_ones() as usize;
+					self[i] |= rhs[i];
+				}
+				delta
+			}
+			#[inline]
+			fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
 				assert_eq!(self.len(), rhs.len());
+				let mut delta = 0;
 				for i in 0..self.len() {
-					self[i] ^= rhs[i];
+					delta += (self[i] & rhs[i]).count_ones() as usize;
+					self[i] &= !rhs[i];
 				}
+				delta
+			}
+			#[inline]
+			fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(self.len(), rhs.len());
+				zip_wide(self, rhs, |x, y| x ^ y, |x, y| x ^ y);
 				self
 			}
 			#[inline]
 			fn bit_not(&mut self) -> &mut Self {
+				map_wide(self, |x| !x, |x| !x);
+				self
+			}
+			#[inline]
+			fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+				let mask = (0 as $elem_ty).wrapping_sub(condition as $elem_ty);
 				for i in 0..self.len() {
-					self[i] = !self[i];
+					self[i] ^= mask;
 				}
 				self
 			}
@@ -135,24 +534,443 @@ macro_rules! impl_bit_set_slice {
 				}
 				self
 			}
+			#[inline]
+			fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, mask.len());
+				for i in 0..len {
+					self[i] = self[i] & mask[i] | rhs[i] & !mask[i];
+				}
+				self
+			}
+			#[inline]
+			fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+				let len = self.len();
+				assert_eq!(len, rhs.len());
+				assert_eq!(len, cond.len());
+				for i in 0..len {
+					self[i] |= rhs[i] & cond[i];
+				}
+				self
+			}
+			#[inline]
+			fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+				let len = self.len();
+				assert_eq!(len, a.len());
+				assert_eq!(len, b.len());
+				assert_eq!(len, mask.len());
+				for i in 0..len {
+					self[i] = a[i] & !mask[i] | b[i] & mask[i];
+				}
+				self
+			}
+			#[inline]
+			fn bit_vote_into(&mut self, inputs: &[&Self], threshold: usize) -> &mut Self {
+				for input in inputs {
+					assert_eq!(self.len(), input.len());
+				}
+				for i in 0..self.len() {
+					self[i] = vote(inputs.iter().map(|input| input[i]), inputs.len(), threshold);
+				}
+				self
+			}
+			#[inline]
+			fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					self[i] |= word;
+				}
+				self
+			}
+			#[inline]
+			fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					self[i] &= word;
+				}
+				self
+			}
+			#[inline]
+			fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+				let word = word as $elem_ty;
+				for i in 0..self.len() {
+					self[i] ^= word;
+				}
+				self
+			}
+			#[inline]
+			fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+				assert!(index < self.bit_len());
+				let word = index / $bits_per_word;
+				let below: $elem_ty = !(!0 << index % $bits_per_word);
+				for i in (word + 1..self.len()).rev() {
+					self[i] = self[i] << 1 | self[i - 1] >> ($bits_per_word - 1);
+				}
+				self[word] = self[word] & below | (self[word] & !below) << 1 | (value as $elem_ty) << index % $bits_per_word;
+				self
+			}
+			#[inline]
+			fn bit_remove(&mut self, index: usize) -> bool {
+				assert!(index < self.bit_len());
+				let word = index / $bits_per_word;
+				let below: $elem_ty = !(!0 << index % $bits_per_word);
+				let removed = self[word] >> index % $bits_per_word & 1 != 0;
+				self[word] = self[word] & below | self[word] >> 1 & !below;
+				for i in word + 1..self.len() {
+					self[i - 1] |= self[i] << ($bits_per_word - 1);
+					self[i] >>= 1;
+				}
+				removed
+			}
+			#[inline]
+			fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+				assert!(len <= self.bit_len());
+				let n = cmp::min(n, len);
+				let mut end = len;
+				while end > n {
+					let k = cmp::min($bits_per_word, end - n);
+					let value = extract::<$elem_ty>(self, end - k - n, k);
+					deposit(self, end - k, k, value);
+					end -= k;
+				}
+				self.bit_cond_range(0..n, false)
+			}
+			#[inline]
+			fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				let len = self.bit_len();
+				let n = cmp::min(n, len);
+				let mut start = 0;
+				while start + n < len {
+					let k = cmp::min($bits_per_word, len - n - start);
+					let value = extract::<$elem_ty>(self, start + n, k);
+					deposit(self, start, k, value);
+					start += k;
+				}
+				self.bit_cond_range(len - n..len, fill)
+			}
+			#[inline]
+			fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+				assert!(range.start <= range.end && range.end <= self.bit_len());
+				let (mut start, mut end) = (range.start, range.end);
+				while start + 1 < end {
+					let n = cmp::min($bits_per_word, (end - start) / 2);
+					let x = extract::<$elem_ty>(self, start, n);
+					let y = extract::<$elem_ty>(self, end - n, n);
+					deposit(self, start, n, y.reverse_bits() >> ($bits_per_word - n));
+					deposit(self, end - n, n, x.reverse_bits() >> ($bits_per_word - n));
+					start += n;
+					end -= n;
+				}
+				self
+			}
+			#[inline]
+			fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+				let len = self.bit_len();
+				assert!(group_bits > 0);
+				assert_eq!(len % group_bits, 0);
+				if group_bits.is_power_of_two() && group_bits <= $bits_per_word {
+					// Groups never straddle words, swap ever smaller halves in place
+					for word in self.iter_mut() {
+						let mut x = *word;
+						let mut shift = 1;
+						while shift < group_bits {
+							let mask = !0 / ((1 << shift) + 1);
+							x = (x >> shift) & mask | (x & mask) << shift;
+							shift <<= 1;
+						}
+						*word = x;
+					}
+				}
+				else {
+					for start in (0..len).step_by(group_bits) {
+						self.bit_reverse_range(start..start + group_bits);
+					}
+				}
+				self
+			}
+			#[inline]
+			fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+				assert!(a + len <= self.bit_len() && b + len <= self.bit_len());
+				assert!(len == 0 || a + len <= b || b + len <= a);
+				let mut i = 0;
+				while i < len {
+					let n = cmp::min($bits_per_word, len - i);
+					let x = extract::<$elem_ty>(self, a + i, n);
+					let y = extract::<$elem_ty>(self, b + i, n);
+					deposit(self, a + i, n, y);
+					deposit(self, b + i, n, x);
+					i += n;
+				}
+				self
+			}
+			#[inline]
+			fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+				let len = cmp::max(a.len(), b.len());
+				assert_eq!(self.len(), len);
+				for i in 0..len {
+					let x = if i < a.len() { a[i] } else { 0 };
+					let y = if i < b.len() { b[i] } else { 0 };
+					self[i] = match op {
+						BitOp::Or => x | y,
+						BitOp::And => x & y,
+						BitOp::AndNot => x & !y,
+						BitOp::Xor => x ^ y,
+					};
+				}
+				self
+			}
 
+			#[inline]
+			fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+				assert!(!pattern.is_empty(), "empty tiling pattern");
+				for i in 0..self.len() {
+					self[i] &= pattern[i % pattern.len()];
+				}
+				self
+			}
+			#[inline]
+			fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+				assert!(!pattern.is_empty(), "empty tiling pattern");
+				for i in 0..self.len() {
+					self[i] |= pattern[i % pattern.len()];
+				}
+				self
+			}
+			#[inline]
+			fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+				assert!(!pattern.is_empty(), "empty tiling pattern");
+				for i in 0..self.len() {
+					self[i] ^= pattern[i % pattern.len()];
+				}
+				self
+			}
+			#[inline]
+			fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				let word = rhs[0];
+				for i in 0..self.len() {
+					self[i] |= word;
+				}
+				self
+			}
+			#[inline]
+			fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				let word = rhs[0];
+				for i in 0..self.len() {
+					self[i] &= word;
+				}
+				self
+			}
+			#[inline]
+			fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+				assert_eq!(rhs.len(), 1);
+				let word = rhs[0];
+				for i in 0..self.len() {
+					self[i] ^= word;
+				}
+				self
+			}
 			#[inline]
 			fn bit_count(&self) -> usize {
 				let mut result = 0;
 				for i in 0..self.len() {
-					result += self[i].count_ones() as usize;
+					result += Word::count_ones(self[i]) as usize;
 				}
 				result
 			}
-		}
-	};
-}
 
-impl_bit_set_slice!([u8], 8);
-impl_bit_set_slice!([u16], 16);
-impl_bit_set_slice!([u32], 32);
-impl_bit_set_slice!([u64], 64);
-impl_bit_set_slice!([u128], 128);
+			#[inline]
+			fn bit_count_up_to(&self, limit: usize) -> usize {
+				count_up_to(self.iter().map(|word| Word::count_ones(*word)), limit)
+			}
+			#[inline]
+			fn bit_andnot_count(&self, rhs: &Self) -> usize {
+				assert_eq!(self.len(), rhs.len());
+				let mut result = 0;
+				for i in 0..self.len() {
+					result += (self[i] & !rhs[i]).count_ones() as usize;
+				}
+				result
+			}
+			#[inline]
+			fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+				assert_eq!(self.len(), rhs.len());
+				let counts = self.iter().zip(rhs).map(|(&a, &b)| Word::count_ones(a & b));
+				count_up_to(counts, threshold) >= threshold
+			}
+			#[inline]
+			fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+				assert_eq!(self.len(), rhs.len());
+				sum_at_most(self.iter().zip(rhs).map(|(&a, &b)| Word::count_ones(a ^ b)), max)
+			}
+			#[inline]
+			fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+				// Only visits the bits in the intersection, so it is cheap for sparse overlaps
+				assert_eq!(self.len(), rhs.len());
+				assert_eq!(self.bit_len(), weights.len());
+				let mut result = 0;
+				for i in 0..self.len() {
+					let mut word = self[i] & rhs[i];
+					while word != 0 {
+						result += weights[i * $bits_per_word + word.trailing_zeros() as usize] as u64;
+						word &= word - 1;
+					}
+				}
+				result
+			}
+			#[inline]
+			fn bit_leading_ones(&self) -> usize {
+				let mut result = 0;
+				for i in (0..self.len()).rev() {
+					if self[i] != !0 {
+						return result + self[i].leading_ones() as usize;
+					}
+					result += $bits_per_word;
+				}
+				result
+			}
+			#[inline]
+			fn bit_trailing_ones(&self) -> usize {
+				let mut result = 0;
+				for i in 0..self.len() {
+					if self[i] != !0 {
+						return result + self[i].trailing_ones() as usize;
+					}
+					result += $bits_per_word;
+				}
+				result
+			}
+
+			#[inline]
+			fn bit_find_first_zero(&self) -> Option<usize> {
+				let index = self.iter().position(|&word| word != !0)?;
+				Some(index * $bits_per_word + (!self[index]).trailing_zeros() as usize)
+			}
+			#[inline]
+			fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+				if from >= self.bit_len() {
+					return None;
+				}
+				let mut index = from / $bits_per_word;
+				let mut word = !self[index] & (!0 << from % $bits_per_word);
+				while word == 0 {
+					index += 1;
+					if index >= self.len() {
+						return None;
+					}
+					word = !self[index];
+				}
+				Some(index * $bits_per_word + word.trailing_zeros() as usize)
+			}
+			#[inline]
+			fn bit_select_high(&self, k: usize) -> Option<usize> {
+				let mut k = k;
+				for i in (0..self.len()).rev() {
+					let mut word = self[i];
+					let count = word.count_ones() as usize;
+					if k < count {
+						// Clear the k highest set bits, the target is then the highest
+						for _ in 0..k {
+							word ^= 1 << ($bits_per_word - 1 - word.leading_zeros());
+						}
+						return Some(i * $bits_per_word + ($bits_per_word - 1 - word.leading_zeros() as usize));
+					}
+					k -= count;
+				}
+				None
+			}
+			#[inline]
+			fn bit_summary(&self) -> BitSummary {
+				let mut summary = BitSummary::default();
+				for i in 0..self.len() {
+					let word = self[i];
+					if word != 0 {
+						summary.count += word.count_ones() as usize;
+						if summary.first.is_none() {
+							summary.first = Some(i * $bits_per_word + word.trailing_zeros() as usize);
+						}
+						summary.last = Some(i * $bits_per_word + ($bits_per_word - 1 - word.leading_zeros() as usize));
+					}
+				}
+				summary
+			}
+			#[inline]
+			fn bit_rank_table(&self, out: &mut [usize]) {
+				assert_eq!(out.len(), self.len() + 1);
+				let mut rank = 0;
+				for i in 0..self.len() {
+					out[i] = rank;
+					rank += self[i].count_ones() as usize;
+				}
+				out[self.len()] = rank;
+			}
+
+			#[inline]
+			fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+				if bytes.len() != self.len() * ($bits_per_word / 8) {
+					return Err(LengthError);
+				}
+				for (word, chunk) in self.iter_mut().zip(bytes.chunks_exact($bits_per_word / 8)) {
+					let chunk = chunk.try_into().unwrap();
+					*word = match endian {
+						Endian::Little => <$elem_ty>::from_le_bytes(chunk),
+						Endian::Big => <$elem_ty>::from_be_bytes(chunk),
+					};
+				}
+				Ok(())
+			}
+			#[inline]
+			fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+				if out.len() != self.len() * ($bits_per_word / 8) {
+					return Err(LengthError);
+				}
+				for (word, chunk) in self.iter().zip(out.chunks_exact_mut($bits_per_word / 8)) {
+					chunk.copy_from_slice(&match endian {
+						Endian::Little => word.to_le_bytes(),
+						Endian::Big => word.to_be_bytes(),
+					});
+				}
+				Ok(())
+			}
+
+			#[inline]
+			fn bit_nonzero_words(&self) -> usize {
+				let mut result = 0;
+				for i in 0..self.len() {
+					result += (self[i] != 0) as usize;
+				}
+				result
+			}
+			#[inline]
+			fn bit_first_nonzero_word(&self) -> Option<usize> {
+				self.iter().position(|&word| word != 0)
+			}
+			#[inline]
+			fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, mut f: F) -> B {
+				let mut acc = init;
+				let mut index = 0;
+				for &word in self.iter() {
+					let word = word as u128;
+					let mut shift = 0;
+					while shift < $bits_per_word {
+						acc = f(acc, index, (word >> shift) as u64);
+						index += 1;
+						shift += 64;
+					}
+				}
+				acc
+			}
+		}
+	};
+}
+
+impl_bit_set_slice!([u8], 8);
+impl_bit_set_slice!([u16], 16);
+impl_bit_set_slice!([u32], 32);
+impl_bit_set_slice!([u64], 64);
+impl_bit_set_slice!([u128], 128);
 
 //----------------------------------------------------------------
 
@@ -169,6 +987,1160 @@ fn tests() {
 	super::unary_tests(&mut qwords[..]);
 }
 
+#[test]
+fn test_init_large() {
+	let mut words = vec![0x5555u16; 1 << 19];
+	words.bit_init(true);
+	assert!(words.iter().all(|&word| word == !0));
+	assert_eq!(words.bit_count(), words.bit_len());
+	words.bit_init(false);
+	assert!(words.iter().all(|&word| word == 0));
+	assert_eq!(words.bit_count(), 0);
+}
+
+#[test]
+fn test_combine_into() {
+	let short = [0x0fu8, 0x33];
+	let long = [0x55u8, 0x55, 0xf0, 0x81];
+	let mut dst = [0u8; 4];
+
+	dst.bit_combine_into(&short, &long, BitOp::Or);
+	assert_eq!(dst, [0x5f, 0x77, 0xf0, 0x81]);
+	dst.bit_combine_into(&short, &long, BitOp::And);
+	assert_eq!(dst, [0x05, 0x11, 0x00, 0x00]);
+	dst.bit_combine_into(&short, &long, BitOp::Xor);
+	assert_eq!(dst, [0x5a, 0x66, 0xf0, 0x81]);
+	dst.bit_combine_into(&short, &long, BitOp::AndNot);
+	assert_eq!(dst, [0x0a, 0x22, 0x00, 0x00]);
+	dst.bit_combine_into(&long, &short, BitOp::AndNot);
+	assert_eq!(dst, [0x50, 0x44, 0xf0, 0x81]);
+}
+
+#[test]
+fn test_rotate_left_range() {
+	let original = [0x0123_4567u32, 0x89ab_cdef, 0xdead_beef, 0xf00d_cafe];
+	for &(start, len) in &[(0, 1), (3, 5), (0, 32), (7, 33), (20, 64), (64, 64)] {
+		for &n in &[0, 1, 4, 31, 63, 64, 100] {
+			let mut bits = original;
+			bits.bit_rotate_left_range(start..start + len, n);
+
+			// Reference rotates the extracted window as an integer
+			let mut window = 0u64;
+			for i in 0..len {
+				window |= (original.bit_test(start + i) as u64) << i;
+			}
+			let mask = if len == 64 { !0 } else { (1u64 << len) - 1 };
+			let n = n % len;
+			let rotated = if n == 0 { window } else { (window << n | window >> (len - n)) & mask };
+
+			for i in 0..bits.bit_len() {
+				let expected = if i >= start && i < start + len { rotated >> (i - start) & 1 != 0 } else { original.bit_test(i) };
+				assert_eq!(bits.bit_test(i), expected, "start={} len={} n={} bit={}", start, len, n, i);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_nonzero_words() {
+	let words = [0u16, 0, 0x10, 0, 0, 0xffff, 0, 1];
+	assert_eq!(words.bit_nonzero_words(), 3);
+	assert_eq!(words.bit_first_nonzero_word(), Some(2));
+	assert_eq!(words[3..].bit_first_nonzero_word(), Some(2));
+	assert_eq!([0u64; 4].bit_nonzero_words(), 0);
+	assert_eq!([0u64; 4].bit_first_nonzero_word(), None);
+}
+
+#[test]
+fn test_cond_range() {
+	for &(start, end) in &[(0, 0), (3, 5), (0, 32), (7, 33), (8, 64), (20, 84), (95, 128)] {
+		for &value in &[false, true] {
+			let mut bits = [0x0123_4567u32, 0x89ab_cdef, 0xdead_beef, 0xf00d_cafe];
+			let mut expected = bits;
+			bits.bit_cond_range(start..end, value);
+			for i in start..end {
+				expected.bit_cond(i, value);
+			}
+			assert_eq!(bits, expected, "{}..{} {}", start, end, value);
+		}
+	}
+
+	let mut byte = 0u8;
+	byte.bit_cond_range(2..5, true);
+	assert_eq!(byte, 0b0001_1100);
+	byte.bit_cond_range(0..8, false);
+	assert_eq!(byte, 0);
+}
+
+#[test]
+fn test_broadcast() {
+	let mut bytes = [0x10u8; 4];
+	bytes.bit_or_broadcast(0x01);
+	assert_eq!(bytes, [0x11; 4]);
+	bytes.bit_xor_broadcast(0xff_ff);
+	assert_eq!(bytes, [0xee; 4]);
+	bytes.bit_and_broadcast(0x1_0f);
+	assert_eq!(bytes, [0x0e; 4]);
+
+	let mut dwords = [0x8000_0000u32; 2];
+	dwords.bit_or_broadcast(0xffff_ffff_0000_0001);
+	assert_eq!(dwords, [0x8000_0001; 2]);
+	dwords.bit_and_broadcast(0x1);
+	assert_eq!(dwords, [0x1; 2]);
+
+	let mut oword = !0u128;
+	oword.bit_and_broadcast(!0);
+	assert_eq!(oword, u64::MAX as u128);
+}
+
+#[test]
+fn test_expand_compress() {
+	let bits = [0b1011_0001u8, 0b0110_1101, 0b1000_0011];
+
+	let mut spread = [0xffu8; 6];
+	bits.bit_expand_into(2, &mut spread);
+	assert_eq!(spread, [0b0000_0001, 0b0100_0101, 0b0101_0001, 0b0001_0100, 0b0000_0101, 0b0100_0000]);
+
+	let mut spread3 = [0xffu8; 9];
+	bits.bit_expand_into(3, &mut spread3);
+	let mut spread4 = [0xffu8; 12];
+	bits.bit_expand_into(4, &mut spread4);
+	for i in 0..bits.bit_len() {
+		assert_eq!(spread3.bit_test(i * 3), bits.bit_test(i));
+		assert!(!spread3.bit_test(i * 3 + 1) && !spread3.bit_test(i * 3 + 2));
+	}
+	assert_eq!(spread3.bit_count(), bits.bit_count());
+
+	let mut round = [0u8; 3];
+	spread.bit_compress_into(2, &mut round);
+	assert_eq!(round, bits);
+	spread3.bit_compress_into(3, &mut round);
+	assert_eq!(round, bits);
+	spread4.bit_compress_into(4, &mut round);
+	assert_eq!(round, bits);
+}
+
+#[test]
+fn test_ones() {
+	let bits = [!0u16, !0, 0x7fff, 0, 0xf000, !0];
+	assert_eq!(bits.bit_trailing_ones(), 47);
+	assert_eq!(bits.bit_leading_ones(), 20);
+	assert_eq!([!0u8; 3].bit_trailing_ones(), 24);
+	assert_eq!([!0u8; 3].bit_leading_ones(), 24);
+	assert_eq!([0u64; 2].bit_leading_ones(), 0);
+	assert_eq!(bits.bit_trailing_ones(), (0..bits.bit_len()).take_while(|&i| bits.bit_test(i)).count());
+}
+
+#[test]
+fn test_blend_into() {
+	let a = [0x1234u16, 0x5678, 0x9abc];
+	let b = [0xffffu16, 0x0000, 0xf0f0];
+	let mask = [0x00ffu16, 0xff00, 0x0ff0];
+
+	let mut expected = a;
+	expected.bit_mask(&b, &mask);
+	let mut out = [0u16; 3];
+	out.bit_blend_into(&a, &b, &mask);
+	assert_eq!(out, expected);
+
+	let mut composed = mask;
+	composed.bit_not().bit_and(&a);
+	let mut selected = b;
+	selected.bit_and(&mask);
+	composed.bit_or(&selected);
+	assert_eq!(out, composed);
+
+	let mut out = 0u8;
+	out.bit_blend_into(&0x0f, &0xf0, &0x3c);
+	assert_eq!(out, 0x33);
+}
+
+#[test]
+fn test_is_empty_full() {
+	let mut bits = [0u32; 8];
+	assert!(bits.bit_is_empty() && !bits.bit_is_full());
+	bits.bit_set(255);
+	assert!(!bits.bit_is_empty() && !bits.bit_is_full());
+	bits.bit_init(true);
+	assert!(!bits.bit_is_empty() && bits.bit_is_full());
+	bits.bit_reset(0);
+	assert!(!bits.bit_is_empty() && !bits.bit_is_full());
+
+	assert!(0u8.bit_is_empty());
+	assert!(0xffu8.bit_is_full());
+
+	// Including the containers relying on the default implementations
+	#[track_caller]
+	fn check<T: ?Sized + BitSet>(bits: &mut T) {
+		for i in 0..bits.bit_len() {
+			bits.bit_init(false).bit_set(i);
+			assert!(!bits.bit_is_empty() && (bits.bit_len() == 1) == bits.bit_is_full());
+			bits.bit_init(true).bit_reset(i);
+			assert!(!bits.bit_is_full() && (bits.bit_len() == 1) == bits.bit_is_empty());
+		}
+		assert!(bits.bit_init(false).bit_is_empty());
+		assert!(bits.bit_init(true).bit_is_full());
+	}
+	check(&mut [0u32; 3][..]);
+	check(&mut 0u16);
+	check(&mut [[0u64; 2]; 2][..]);
+	check(&mut [false; 70][..]);
+	check(&mut [false; 1][..]);
+	check(&mut super::BitArray::<100, 2>::new());
+	check(&mut super::CountedBitSet::new(0u128));
+}
+
+#[test]
+fn test_load_store_bytes() {
+	let bytes = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+
+	let mut dwords = [0u32; 2];
+	dwords.bit_load_bytes(&bytes, Endian::Big).unwrap();
+	assert_eq!(dwords, [0x0123_4567, 0x89ab_cdef]);
+	dwords.bit_load_bytes(&bytes, Endian::Little).unwrap();
+	assert_eq!(dwords, [0x6745_2301, 0xefcd_ab89]);
+
+	let mut out = [0u8; 8];
+	dwords.bit_store_bytes(&mut out, Endian::Little).unwrap();
+	assert_eq!(out, bytes);
+	dwords.bit_store_bytes(&mut out, Endian::Big).unwrap();
+	assert_eq!(out, [0x67, 0x45, 0x23, 0x01, 0xef, 0xcd, 0xab, 0x89]);
+
+	// Byte arrays are their own representation regardless of endianness
+	let mut raw = [0u8; 8];
+	raw.bit_load_bytes(&bytes, Endian::Big).unwrap();
+	assert_eq!(raw, bytes);
+
+	let mut qword = 0u64;
+	qword.bit_load_bytes(&bytes, Endian::Big).unwrap();
+	assert_eq!(qword, 0x0123_4567_89ab_cdef);
+
+	assert_eq!(dwords.bit_load_bytes(&bytes[1..], Endian::Little), Err(LengthError));
+	assert_eq!(dwords, [0x6745_2301, 0xefcd_ab89]);
+	assert_eq!(dwords.bit_store_bytes(&mut [0u8; 9], Endian::Little), Err(LengthError));
+}
+
+#[test]
+fn test_rank_table() {
+	let bits = [0x0123_4567u32, 0, 0x89ab_cdef, !0, 0x8000_0001];
+	let mut table = [0usize; 6];
+	bits.bit_rank_table(&mut table);
+	assert_eq!(table, [0, 12, 12, 32, 64, 66]);
+
+	let mut count = 0;
+	for i in 0..bits.bit_len() {
+		let word = bits[i / 32] & ((1u64 << (i % 32)) - 1) as u32;
+		assert_eq!(table[i / 32] + word.count_ones() as usize, count);
+		count += bits.bit_test(i) as usize;
+	}
+	assert_eq!(table[5], count);
+
+	let mut table = [0usize; 2];
+	0xf0u8.bit_rank_table(&mut table);
+	assert_eq!(table, [0, 4]);
+}
+
+#[test]
+fn test_masked() {
+	let required = [0b1010_0101u8, 0b0000_1111];
+	let available = [0b1110_0100u8, 0b1111_0111];
+	let dont_care = [0b0000_0001u8, 0b0000_1000];
+	let mut care = dont_care;
+	care.bit_not();
+
+	assert!(!required.bit_subset(&available));
+	assert!(required.bit_subset_masked(&available, &care));
+	assert!(!required.bit_subset_masked(&available, &[!0; 2]));
+
+	assert!(!required.bit_eq_masked(&available, &care));
+	assert!(required.bit_eq_masked(&available, &[0b1010_0100, 0b0000_0111]));
+	assert!(required.bit_eq_masked(&available, &[0; 2]));
+
+	assert!(0xf0u8.bit_eq_masked(&0xff, &0xf0));
+	assert!(!0xf0u8.bit_eq_masked(&0xff, &0xf8));
+	assert!(0x0fu8.bit_subset_masked(&0x07, &0xf7));
+}
+
+#[test]
+fn test_swap_ranges() {
+	let original = [0x0123_4567u32, 0x89ab_cdef, 0xdead_beef, 0xf00d_cafe];
+	for &(a, b, len) in &[(0, 32, 32), (64, 0, 64), (3, 40, 5), (1, 70, 57), (100, 13, 28), (5, 6, 1), (9, 9, 0)] {
+		let mut bits = original;
+		bits.bit_swap_ranges(a, b, len);
+
+		// Reference extracts both spans and deposits them swapped
+		let mut expected = original;
+		for i in 0..len {
+			expected.bit_cond(a + i, original.bit_test(b + i));
+			expected.bit_cond(b + i, original.bit_test(a + i));
+		}
+		assert_eq!(bits, expected, "a={} b={} len={}", a, b, len);
+
+		let mut bytes = [0u8; 16];
+		for (i, word) in original.iter().enumerate() {
+			bytes[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+		}
+		bytes.bit_swap_ranges(a, b, len);
+		assert!((0..128).all(|i| bytes.bit_test(i) == expected.bit_test(i)));
+	}
+
+	let mut byte = 0b1100_0011u8;
+	byte.bit_swap_ranges(0, 4, 4);
+	assert_eq!(byte, 0b0011_1100);
+}
+
+#[test]
+fn test_weighted_overlap() {
+	let a = [0x0123_4567u32, 0x89ab_cdef, 0, !0];
+	let b = [0xffff_0000u32, 0x0f0f_0f0f, !0, 0x8000_0001];
+	let mut weights = [0u32; 128];
+	for (i, weight) in weights.iter_mut().enumerate() {
+		*weight = (i * i) as u32 + 1;
+	}
+
+	let mut expected = 0;
+	for (i, &weight) in weights.iter().enumerate() {
+		if a.bit_test(i) && b.bit_test(i) {
+			expected += weight as u64;
+		}
+	}
+	assert_eq!(a.bit_weighted_overlap(&b, &weights), expected);
+	assert_eq!(a.bit_weighted_overlap(&[0; 4], &weights), 0);
+	assert_eq!(0x0fu8.bit_weighted_overlap(&0x3c, &[1, 2, 4, 8, 16, 32, 64, 128]), 12);
+}
+
+#[test]
+fn test_find_zero() {
+	let mut slots = [!0u32; 4];
+	assert_eq!(slots.bit_find_first_zero(), None);
+	assert_eq!(slots.bit_find_next_zero(0), None);
+
+	slots.bit_reset(77);
+	assert_eq!(slots.bit_find_first_zero(), Some(77));
+	assert_eq!(slots.bit_find_next_zero(0), Some(77));
+	assert_eq!(slots.bit_find_next_zero(64), Some(77));
+	assert_eq!(slots.bit_find_next_zero(77), Some(77));
+	assert_eq!(slots.bit_find_next_zero(78), None);
+	assert_eq!(slots.bit_find_next_zero(128), None);
+
+	assert_eq!(0xffu8.bit_find_first_zero(), None);
+	assert_eq!(0xefu8.bit_find_next_zero(3), Some(4));
+	assert_eq!(0xefu8.bit_find_next_zero(5), None);
+	assert_eq!([[!0u64, 0x7f], [0, 0]].bit_find_next_zero(1), Some(71));
+}
+
+#[test]
+fn test_summary() {
+	fn check<T: ?Sized + BitSet>(bits: &T) {
+		let summary = bits.bit_summary();
+		assert_eq!(summary.count, bits.bit_count());
+		assert_eq!(summary.first, (0..bits.bit_len()).find(|&i| bits.bit_test(i)));
+		assert_eq!(summary.last, (0..bits.bit_len()).rev().find(|&i| bits.bit_test(i)));
+	}
+	check(&[0u16; 3][..]);
+	check(&[0u16, 0x0100, 0x8001][..]);
+	check(&[0x8000_0000u32, 0, 1][..]);
+	check(&0x0ff0u16);
+	check(&[[0u32, 0x10, 0, 0], [0, 0, 0x4000, 0]][..]);
+	assert_eq!(0u8.bit_summary(), BitSummary { count: 0, first: None, last: None });
+	assert_eq!([0u8, 0x24].bit_summary(), BitSummary { count: 2, first: Some(10), last: Some(13) });
+}
+
+#[test]
+fn test_test_and_set() {
+	let mut lock = [0u16; 3];
+	assert!(!lock.bit_test_and_set(37));
+	assert!(lock.bit_test_and_set(37));
+	assert_eq!(lock, [0, 0, 0x20]);
+	assert!(lock.bit_test_and_reset(37));
+	assert!(!lock.bit_test_and_reset(37));
+	assert_eq!(lock, [0, 0, 0]);
+
+	let mut word = 0u8;
+	assert!(!word.bit_test_and_set(7));
+	assert!(word.bit_test_and_set(7));
+	assert_eq!(word, 0x80);
+
+	let mut lanes = [[0u32; 4]; 2];
+	assert!(!lanes.bit_test_and_set(200));
+	assert!(lanes.bit_test_and_set(200));
+	assert!(lanes.bit_test_and_reset(200));
+	assert!(lanes.bit_none());
+	assert!(lanes.bit_set_once(17));
+	assert!(!lanes.bit_set_once(17));
+	assert!(lanes.bit_test(17));
+}
+
+#[test]
+fn test_not_cond() {
+	let mut bits = [0x0f0f_1234u32, 0];
+	bits.bit_not_cond(false);
+	assert_eq!(bits, [0x0f0f_1234, 0]);
+	bits.bit_not_cond(true);
+	assert_eq!(bits, [0xf0f0_edcb, !0]);
+
+	let mut word = 0x5au8;
+	assert_eq!(*word.bit_not_cond(true), 0xa5);
+	assert_eq!(*word.bit_not_cond(false), 0xa5);
+
+	let mut lanes = [[0u16; 8]; 2];
+	lanes.bit_not_cond(true);
+	assert!(lanes.bit_all());
+	lanes.bit_not_cond(false);
+	assert!(lanes.bit_all());
+}
+
+#[test]
+fn test_disjoint_range() {
+	let a = [0x0000_00f0u32, 0, 0x8000_0000, 1];
+	let b = [0x0000_0f10u32, !0, 0x8000_0000, 0];
+
+	assert!(a.bit_disjoint_range(&b, 0..4));
+	assert!(!a.bit_disjoint_range(&b, 0..5));
+	assert!(a.bit_disjoint_range(&b, 5..95));
+	assert!(!a.bit_disjoint_range(&b, 5..96));
+	assert!(!a.bit_disjoint_range(&b, 95..96));
+	assert!(a.bit_disjoint_range(&b, 96..128));
+	assert!(a.bit_disjoint_range(&b, 4..4));
+	assert!(!a.bit_disjoint_range(&b, 0..128));
+
+	assert!(0x0fu8.bit_disjoint_range(&0xf8, 0..3));
+	assert!(!0x0fu8.bit_disjoint_range(&0xf8, 0..4));
+	let lanes = [[0u64, 1], [0, 0]];
+	assert!(!lanes.bit_disjoint_range(&[[0, !0], [0, 0]], 64..65));
+	assert!(lanes.bit_disjoint_range(&[[0, !0], [0, 0]], 65..256));
+}
+
+#[test]
+fn test_tiled() {
+	let pattern = [0x0fu8, 0x81, 0xff];
+	let bits = [0x5au8; 8];
+	let tile = |bits: &[u8], op: fn(u8, u8) -> u8| {
+		let mut result = [0u8; 8];
+		for i in 0..bits.len() {
+			result[i] = op(bits[i], pattern[i % pattern.len()]);
+		}
+		result
+	};
+	assert_eq!(*bits.clone().bit_and_tiled(&pattern), tile(&bits, |a, b| a & b));
+	assert_eq!(*bits.clone().bit_or_tiled(&pattern), tile(&bits, |a, b| a | b));
+	assert_eq!(*bits.clone().bit_xor_tiled(&pattern), tile(&bits, |a, b| a ^ b));
+
+	let mut lanes = [[!0u32; 4]; 3];
+	lanes.bit_and_tiled(&[[0x5555_5555; 4]]);
+	assert_eq!(lanes, [[0x5555_5555; 4]; 3]);
+
+	let mut word = 0u32;
+	assert_eq!(*word.bit_or_tiled(&0x8000_0001), 0x8000_0001);
+}
+
+#[test]
+fn test_reverse_range() {
+	let original = [0x1234_5678u32, 0x9abc_def0, 0x0f1e_2d3c, 0x4b5a_6978];
+	for &(start, end) in &[(0, 128), (3, 3), (5, 6), (7, 30), (17, 111), (32, 96), (1, 128)] {
+		let mut bits = original;
+		bits.bit_reverse_range(start..end);
+		for i in 0..128 {
+			let j = if i >= start && i < end { start + end - 1 - i } else { i };
+			assert_eq!(bits.bit_test(i), original.bit_test(j));
+		}
+		bits.bit_reverse_range(start..end);
+		assert_eq!(bits, original);
+	}
+
+	let mut bits = [0u16; 4];
+	bits.bit_set(20).bit_reverse_range(10..50);
+	assert_eq!(bits.bit_summary().first, Some(39));
+	assert_eq!(*0x01u8.bit_reverse_range(0..8), 0x80);
+	assert_eq!(*0x01u8.bit_reverse_range(0..3), 0x04);
+}
+
+#[test]
+fn test_andnot_range() {
+	let original = [0xffff_ffffu32, 0x1234_5678, 0xffff_0000, 0x0f0f_0f0f];
+	let stencil = [0xaaaa_aaaau32, 0xffff_ffff, 0x5555_5555, 0x3333_3333];
+	for &(start, end) in &[(0, 128), (3, 3), (5, 6), (7, 30), (17, 111), (32, 96)] {
+		let mut expected = original;
+		expected.bit_andnot(&stencil);
+		for i in (0..start).chain(end..128) {
+			expected.bit_cond(i, original.bit_test(i));
+		}
+		let mut bits = original;
+		bits.bit_andnot_range(&stencil, start..end);
+		assert_eq!(bits, expected);
+	}
+
+	assert_eq!(*0xffu8.bit_andnot_range(&0xff, 2..6), 0xc3);
+	let mut lanes = [[!0u64; 2]; 2];
+	lanes.bit_andnot_range(&[[!0; 2]; 2], 60..130);
+	assert_eq!(lanes.bit_count(), 256 - 70);
+}
+
+#[test]
+fn test_all_any_len() {
+	let mut bits = [0u32; 4];
+	bits.bit_cond_range(0..100, true);
+	assert!(bits.bit_all_len(100));
+	assert!(bits.bit_all_len(64));
+	assert!(bits.bit_all_len(0));
+	assert!(!bits.bit_all_len(101));
+	assert!(!bits.bit_all());
+
+	let mut bits = [0u32; 4];
+	assert!(!bits.bit_any_len(128));
+	bits.bit_set(99);
+	assert!(!bits.bit_any_len(99));
+	assert!(bits.bit_any_len(100));
+	assert!(!bits.bit_any_len(0));
+
+	assert!(0x0fu8.bit_all_len(4));
+	assert!(!0x0fu8.bit_all_len(5));
+	assert!(!0xf0u8.bit_any_len(4));
+	let lanes = [[!0u64, 1], [0, 0]];
+	assert!(lanes.bit_all_len(65));
+	assert!(!lanes.bit_all_len(66));
+}
+
+#[test]
+fn test_wide() {
+	let mut a = [0u8; 67];
+	let mut b = [0u8; 67];
+	for i in 0..67 {
+		a[i] = (i * 37 + 11) as u8;
+		b[i] = (i * 101 + 3) as u8;
+	}
+	for &(x, y) in &[(0, 0), (1, 1), (3, 3), (0, 5), (2, 7)] {
+		let len = 60;
+		let (a, b) = (&a[x..x + len], &b[y..y + len]);
+		let mut or = [0u8; 60];
+		let mut and = [0u8; 60];
+		let mut xor = [0u8; 60];
+		let mut not = [0u8; 60];
+		for i in 0..len {
+			or[i] = a[i] | b[i];
+			and[i] = a[i] & b[i];
+			xor[i] = a[i] ^ b[i];
+			not[i] = !a[i];
+		}
+		let mut buf = [0u8; 67];
+		let dst = &mut buf[x..x + len];
+		dst.copy_from_slice(a);
+		assert_eq!(dst.bit_or(b), &or[..]);
+		dst.copy_from_slice(a);
+		assert_eq!(dst.bit_and(b), &and[..]);
+		dst.copy_from_slice(a);
+		assert_eq!(dst.bit_xor(b), &xor[..]);
+		dst.copy_from_slice(a);
+		assert_eq!(dst.bit_not(), &not[..]);
+	}
+
+	let mut words = [0x1234u16; 9];
+	words.bit_xor(&[0xffff; 9]);
+	assert_eq!(words, [0xedcb; 9]);
+
+	// Aligned alike but the middles differ in length, combines word by word
+	let mut dst = super::Aligned16([0u8; 32]);
+	let src = super::Aligned16([0xffu8; 32]);
+	zip_wide(&mut dst.0[..24], &src.0[..20], |x, y| x | y, |x, y| x | y);
+	assert_eq!(&dst.0[..20], &[0xff; 20]);
+	assert_eq!(&dst.0[20..], &[0; 12]);
+}
+
+#[test]
+fn test_is_empty_chunked() {
+	let mut bits = [0u64; 1000];
+	assert!(bits.bit_is_empty());
+	for &i in &[0, 7, 8, 500, 999] {
+		bits[i] = 1 << (i % 64);
+		assert!(!bits.bit_is_empty());
+		assert_eq!(bits.bit_is_empty(), bits.iter().all(|&word| word == 0));
+		bits[i] = 0;
+	}
+
+	let mut bits = [!0u16; 21];
+	assert!(bits.bit_is_full());
+	bits[20] = 0x7fff;
+	assert!(!bits.bit_is_full());
+	assert!(bits[..0].bit_is_empty() && bits[..0].bit_is_full());
+}
+
+#[test]
+fn test_mask_inv() {
+	let bits = [0x1234_5678u32, 0x9abc_def0];
+	let rhs = [0x0f0f_0f0fu32, 0xffff_0000];
+	let mask = [0xff00_ff00u32, 0x0ff0_0ff0];
+
+	let mut expected = [0u32; 2];
+	for i in 0..2 {
+		expected[i] = bits[i] & !mask[i] | rhs[i] & mask[i];
+	}
+	assert_eq!(*bits.clone().bit_mask(&rhs, &mask), expected);
+	for i in 0..2 {
+		expected[i] = bits[i] & mask[i] | rhs[i] & !mask[i];
+	}
+	assert_eq!(*bits.clone().bit_mask_inv(&rhs, &mask), expected);
+
+	assert_eq!(*0x12u8.bit_mask_inv(&0xab, &0xf0), 0x1b);
+	let mut lanes = [[0u64; 2]; 1];
+	lanes.bit_mask_inv(&[[!0; 2]], &[[0xff, 0]]);
+	assert_eq!(lanes, [[!0xff, !0]]);
+}
+
+#[test]
+fn test_andnot_count() {
+	let a = [0x0123_4567u32, 0x89ab_cdef, 0, !0];
+	let b = [0xffff_0000u32, 0x0f0f_0f0f, !0, 0x8000_0001];
+	assert_eq!(a.bit_andnot_count(&b), a.clone().bit_andnot(&b).bit_count());
+	assert_eq!(b.bit_andnot_count(&a), b.clone().bit_andnot(&a).bit_count());
+	assert_eq!(0xf0u8.bit_andnot_count(&0x3c), 2);
+	assert_eq!([[!0u64; 2]; 2].bit_andnot_count(&[[0, !0], [1, 0]]), 191);
+	assert_eq!([true, true, false].bit_andnot_count(&[false, true, true]), 1);
+}
+
+#[test]
+fn test_shl_len() {
+	let original = [!0u32, 0x1234_5678, 0x9abc_def0, 0xf000_0000];
+	for &n in &[0, 1, 7, 32, 45, 99, 100, 200] {
+		let mut bits = original;
+		bits.bit_shl_len(n, 100);
+		for i in 0..128 {
+			let expected = if i >= 100 { original.bit_test(i) } else { i >= n && original.bit_test(i - n) };
+			assert_eq!(bits.bit_test(i), expected);
+		}
+	}
+
+	let mut bits = [0u32; 4];
+	bits.bit_cond_range(0..100, true).bit_shl_len(3, 100);
+	assert_eq!(bits.bit_count(), 97);
+	assert_eq!(bits[3], 0xf);
+	assert_eq!(*0x81u8.bit_shl_len(1, 8), 0x02);
+	assert_eq!(*0x81u8.bit_shl_len(1, 4), 0x82);
+}
+
+#[test]
+fn test_word_set() {
+	let mut bits = [0x30u8; 16];
+	bits.bit_or_word_set(&[0x0f]);
+	assert_eq!(bits, [0x3f; 16]);
+	bits.bit_and_word_set(&[0x1e]);
+	assert_eq!(bits, [0x1e; 16]);
+	bits.bit_xor_word_set(&[0xff]);
+	assert_eq!(bits, [0xe1; 16]);
+
+	let mut lanes = [[0u32; 4]; 3];
+	lanes.bit_or_word_set(&[[1, 2, 3, 4]]);
+	assert_eq!(lanes, [[1, 2, 3, 4]; 3]);
+	assert_eq!(*0x0fu8.bit_xor_word_set(&0xff), 0xf0);
+}
+
+#[test]
+#[should_panic]
+fn test_word_set_len() {
+	[0u8; 16].bit_or_word_set(&[0x0f, 0x0f]);
+}
+
+#[test]
+fn test_or_and() {
+	let bits = [0x1234_5678u32, 0x9abc_def0, 0];
+	let rhs = [0x0f0f_0f0fu32, 0xffff_0000, !0];
+	let cond = [0xff00_ff00u32, 0x0ff0_0ff0, 0x8000_0001];
+
+	let mut expected = bits;
+	let mut scratch = rhs;
+	expected.bit_or(scratch.bit_and(&cond));
+	assert_eq!(*bits.clone().bit_or_and(&rhs, &cond), expected);
+
+	assert_eq!(*0x01u8.bit_or_and(&0xf0, &0x3c), 0x31);
+	let mut lanes = [[0u64; 2]; 1];
+	lanes.bit_or_and(&[[!0, 0xff]], &[[0xf0, !0]]);
+	assert_eq!(lanes, [[0xf0, 0xff]]);
+}
+
+#[test]
+fn test_set_bits() {
+	// Clustered, unsorted and duplicate indices
+	let indices = [0, 1, 2, 3, 31, 32, 33, 95, 94, 93, 5, 5, 64, 2, 127];
+	let mut expected = [0u32; 4];
+	for &bit in &indices {
+		expected.bit_set(bit);
+	}
+	assert_eq!(*[0u32; 4].bit_set_bits(&indices), expected);
+	assert_eq!(*[0u32; 4].bit_set_bits(&[]), [0; 4]);
+
+	let mut bytes = [0u8; 16];
+	bytes.bit_set_bits(&indices);
+	assert!((0..128).all(|i| bytes.bit_test(i) == expected.bit_test(i)));
+	assert_eq!(*0u64.bit_set_bits(&[63, 0, 7]), 0x8000_0000_0000_0081);
+	let mut lanes = [[0u32; 4]; 1];
+	lanes.bit_set_bits(&indices);
+	assert_eq!(lanes[0], expected);
+}
+
+#[test]
+fn test_similarity() {
+	let a = [0xffu8, 0x0f, 0x00];
+	let b = [0x0fu8, 0xff, 0x01];
+	// 8 shared bits out of 17 distinct, 12 and 13 bits set
+	assert_eq!(a.bit_jaccard(&b), 8.0 / 17.0);
+	assert_eq!(a.bit_dice(&b), 16.0 / 25.0);
+	assert_eq!(a.bit_jaccard(&a), 1.0);
+	assert_eq!(a.bit_dice(&a), 1.0);
+	assert_eq!(a.bit_jaccard(&[0, 0xf0, 0]), 0.0);
+	assert_eq!([0u32; 2].bit_jaccard(&[0; 2]), 0.0);
+	assert_eq!([0u32; 2].bit_dice(&[0; 2]), 0.0);
+	assert_eq!([[0x0fu64, 0xf0]].bit_jaccard(&[[0xff, 0xff]]), 0.5);
+}
+
+#[test]
+fn test_test_and_flip() {
+	fn check<T: ?Sized + BitSet>(bits: &mut T, bit: usize) {
+		let orig = bits.bit_test(bit);
+		assert_eq!(bits.bit_test_and_flip(bit), orig);
+		assert_eq!(bits.bit_test(bit), !orig);
+		assert_eq!(bits.bit_test_and_flip(bit), !orig);
+		assert_eq!(bits.bit_test(bit), orig);
+	}
+	let mut word = 0x8000_0001u32;
+	let mut words = [0x0f0fu16; 4];
+	let mut lanes = [[0x5555_5555u32; 4]; 2];
+	for bit in 0..32 {
+		check(&mut word, bit);
+		check(&mut words[..], bit * 2);
+		check(&mut lanes[..], bit * 8 + 1);
+	}
+	assert_eq!(word, 0x8000_0001);
+	assert_eq!(words, [0x0f0f; 4]);
+	assert_eq!(lanes, [[0x5555_5555; 4]; 2]);
+}
+
+#[test]
+fn test_reverse_groups() {
+	fn reference(bits: &[u8], group_bits: usize) -> [u8; 24] {
+		let mut out = [0u8; 24];
+		for i in 0..bits.bit_len() {
+			let group = i / group_bits * group_bits;
+			out.bit_cond(group + group_bits - 1 - i % group_bits, bits.bit_test(i));
+		}
+		out
+	}
+	let mut bytes = [0u8; 24];
+	for (i, byte) in bytes.iter_mut().enumerate() {
+		*byte = (i as u8).wrapping_mul(0x9d) ^ 0x5a;
+	}
+	for &group_bits in &[1, 2, 3, 4, 6, 8, 12, 16, 64, 192] {
+		let expected = reference(&bytes, group_bits);
+		assert_eq!(*bytes.clone().bit_reverse_groups(group_bits), expected);
+
+		let mut words = [0u32; 6];
+		words.bit_load_bytes(&bytes, Endian::Little).unwrap();
+		words.bit_reverse_groups(group_bits);
+		let mut out = [0u8; 24];
+		words.bit_store_bytes(&mut out, Endian::Little).unwrap();
+		assert_eq!(out, expected);
+	}
+
+	assert_eq!(*0x1234_5678u32.bit_reverse_nibbles(), 0x84c2_a6e1);
+	assert_eq!(*0x1234_5678u32.bit_reverse_groups(8), 0x482c_6a1e);
+	let mut lanes = [[0x1234_5678u32; 4]; 1];
+	lanes.bit_reverse_nibbles();
+	assert_eq!(lanes, [[0x84c2_a6e1; 4]]);
+}
+
+#[test]
+fn test_count_up_to() {
+	let mut dense = [!0u64; 1024];
+	dense[1023] = 0;
+	let bytes = [0x00u8, 0x01, 0x00, 0x30, 0x00, 0x80];
+	let lanes = [[0u32, 1, 0, 0], [0x30, 0, 0, 0x8000_0000]];
+	for limit in 0..8 {
+		let expected = cmp::min(limit, 4);
+		assert_eq!(bytes.bit_count_up_to(limit), expected);
+		assert_eq!(lanes.bit_count_up_to(limit), expected);
+		assert_eq!(0x8000_3100u32.bit_count_up_to(limit), expected);
+		assert_eq!(dense.bit_count_up_to(limit), limit);
+	}
+	assert_eq!(dense.bit_count_up_to(usize::MAX), 1023 * 64);
+	assert_eq!([0u16; 0].bit_count_up_to(3), 0);
+
+	// Stops visiting words once the limit is reached
+	for (limit, expected) in [(0, 0), (1, 1), (64, 1), (65, 2), (640, 10), (usize::MAX, 1024)] {
+		let mut visits = 0;
+		count_up_to(dense.iter().map(|word| word.count_ones()).inspect(|_| visits += 1), limit);
+		assert_eq!(visits, expected, "limit={}", limit);
+	}
+}
+
+#[test]
+fn test_delta() {
+	fn check<T: ?Sized + BitSet>(bits: &mut T, rhs: &T) {
+		let before = bits.bit_count();
+		let added = bits.bit_or_delta(rhs);
+		assert_eq!(bits.bit_count(), before + added);
+		let removed = bits.bit_andnot_delta(rhs);
+		assert_eq!(removed, rhs.bit_count());
+		assert_eq!(bits.bit_count(), before + added - removed);
+		assert!(bits.bit_disjoint(rhs));
+	}
+	let mut bytes = [0x0fu8, 0xf0, 0x00, 0xff];
+	check(&mut bytes[..], &[0xff, 0x0f, 0x01, 0x00]);
+	assert_eq!(bytes, [0x00, 0xf0, 0x00, 0xff]);
+	check(&mut 0x1234_5678u32, &0xff00_ff00);
+	check(&mut [[0x5555u16; 8]; 2][..], &[[0xff00; 8]; 2]);
+
+	let mut words = [0x0fu32, 0];
+	assert_eq!(words.bit_or_delta(&[0xff, 0x1]), 5);
+	assert_eq!(words.bit_or_delta(&[0xff, 0x1]), 0);
+	assert_eq!(words.bit_andnot_delta(&[0xf000_000f, 0x3]), 5);
+	assert_eq!(words, [0xf0, 0]);
+}
+
+#[test]
+fn test_fold_words() {
+	fn count<T: ?Sized + BitSet>(bits: &T) -> usize {
+		bits.bit_fold_words(0, |acc, _, word| acc + word.count_ones() as usize)
+	}
+	let bytes = [0x0fu8, 0xf0, 0x81, 0xff, 0x00];
+	assert_eq!(count(&bytes[..]), bytes.bit_count());
+	assert_eq!(count(&[0x8000_0000_0000_0001u128, !0][..]), 130);
+	assert_eq!(count(&0x1234_5678u32), 0x1234_5678u32.bit_count());
+	assert_eq!(count(&[[0x5555u16; 8]; 2][..]), 128);
+	assert_eq!(count(&[true, false, true][..]), 2);
+
+	let indices = [0x1u128 << 64 | 0x2, 0x3].bit_fold_words([0; 4], |mut acc, index, word| { acc[index] = word; acc });
+	assert_eq!(indices, [0x2, 0x1, 0x3, 0]);
+	let mut bools = [false; 70];
+	bools[64] = true;
+	assert_eq!(bools.bit_fold_words((0, 0), |_, index, word| (index, word)), (1, 1));
+}
+
+#[test]
+fn test_vote() {
+	let mut random = super::xorshift(0x2545_f491);
+	let mut inputs = [[0u16; 5]; 9];
+	for input in inputs.iter_mut() {
+		for word in input.iter_mut() {
+			*word = random() as u16;
+		}
+	}
+	let all: [&[u16]; 9] = [&inputs[0], &inputs[1], &inputs[2], &inputs[3], &inputs[4], &inputs[5], &inputs[6], &inputs[7], &inputs[8]];
+	for n in 0..=all.len() {
+		let refs = &all[..n];
+		for threshold in 0..=n + 1 {
+			let mut out = [0x1234u16; 5];
+			out.bit_vote_into(refs, threshold);
+			for i in 0..out.bit_len() {
+				let votes = refs.iter().filter(|input| input.bit_test(i)).count();
+				assert_eq!(out.bit_test(i), votes >= threshold);
+			}
+		}
+	}
+
+	// Majority of three
+	let mut out = [[0u32; 4]; 1];
+	out.bit_vote_into(&[&[[0b0011; 4]], &[[0b0110; 4]], &[[0b1100; 4]]], 3 / 2 + 1);
+	assert_eq!(out, [[0b0110; 4]]);
+	let mut out = core::num::Wrapping(0u64);
+	out.bit_vote_into(&[&core::num::Wrapping(!0), &core::num::Wrapping(0xff)], 2);
+	assert_eq!(out.0, 0xff);
+}
+
+#[test]
+fn test_test_at() {
+	fn check<T: ?Sized + BitSet>(bits: &mut T, word_bits: usize) {
+		for i in 0..bits.bit_len() {
+			assert_eq!(bits.bit_test_at(i / word_bits, (i % word_bits) as u32), bits.bit_test(i));
+		}
+		for i in (0..bits.bit_len()).step_by(7) {
+			bits.bit_set_at(i / word_bits, (i % word_bits) as u32);
+			assert!(bits.bit_test(i));
+		}
+		assert_eq!(bits.bit_count(), (0..bits.bit_len()).filter(|&i| i % 7 == 0 || i % 5 == 0).count());
+	}
+	let mut bytes = [0u8; 16];
+	let mut words = [0u32; 4];
+	let mut lanes = [[0u16; 8]; 1];
+	let mut wide = [0u128; 1];
+	let mut bools = [false; 128];
+	for i in (0..128).step_by(5) {
+		bytes.bit_set(i);
+		words.bit_set(i);
+		lanes.bit_set(i);
+		wide.bit_set(i);
+		bools.bit_set(i);
+	}
+	check(&mut bytes[..], 8);
+	check(&mut words[..], 32);
+	check(&mut lanes[..], 16);
+	check(&mut wide[..], 128);
+	check(&mut bools[..], 1);
+	check(&mut super::CountedBitSet::new(0x1084_2108_4210_8421u64), 64);
+	check(&mut 0x1084_2108_4210_8421u64, 64);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "bit index out of range")]
+fn test_test_at_out_of_range() {
+	[0u32; 2].bit_test_at(0, 35);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "bit index out of range")]
+fn test_set_at_out_of_range() {
+	[[0u16; 8]; 1].bit_set_at(0, 16);
+}
+
+#[test]
+fn test_same_operand() {
+	let bits = [0x0123_4567u32, 0x89ab_cdef];
+	assert!(bits.bit_eq(&bits) && bits.bit_subset(&bits));
+	assert!(bits[..1].bit_eq(&bits[..1]) && bits[1..].bit_subset(&bits[1..]));
+	assert!(!bits[..].bit_subset(&[0x0123_4567, 0]));
+	let lanes = [[0x5555u16; 8]; 2];
+	assert!(lanes.bit_eq(&lanes));
+}
+
+#[test]
+fn test_densest_word() {
+	let words = [0u32, 0x0000_0100, 0xffff_0000, 0x0f0f_0f0f, 0xffff_0000, 0x8000_0001];
+	assert_eq!(words.bit_densest_word(), Some((2, 16)));
+	assert_eq!(words.bit_sparsest_nonzero_word(), Some((1, 1)));
+	assert_eq!([0u64; 4].bit_densest_word(), None);
+	assert_eq!([0u64; 4].bit_sparsest_nonzero_word(), None);
+	assert_eq!([0u8; 0].bit_densest_word(), None);
+	assert_eq!(0x0300u16.bit_densest_word(), Some((0, 2)));
+
+	let lanes = [[0x1u32, 0, 0xff, 0x7], [0xffff, 0x3, 0, 0]];
+	assert_eq!(lanes.bit_densest_word(), Some((4, 16)));
+	assert_eq!(lanes.bit_sparsest_nonzero_word(), Some((0, 1)));
+}
+
+#[test]
+fn test_insert_remove() {
+	fn check<T: ?Sized + BitSet>(bits: &mut T) {
+		let mut reference = [false; 256];
+		let len = bits.bit_len();
+		let mut random = super::xorshift(0x9e37_79b9);
+		for _ in 0..300 {
+			let seed = random();
+			let index = seed as usize % len;
+			if seed & 0x4000_0000 != 0 {
+				let value = seed & 0x8000_0000 != 0;
+				bits.bit_insert(index, value);
+				reference.copy_within(index..len - 1, index + 1);
+				reference[index] = value;
+			}
+			else {
+				assert_eq!(bits.bit_remove(index), reference[index]);
+				reference.copy_within(index + 1..len, index);
+				reference[len - 1] = false;
+			}
+			assert!((0..len).all(|i| bits.bit_test(i) == reference[i]));
+			assert_eq!(bits.bit_count(), reference.iter().filter(|&&bit| bit).count());
+		}
+	}
+	check(&mut [0u8; 5][..]);
+	check(&mut [0u32; 3][..]);
+	check(&mut [0u128; 2][..]);
+	check(&mut [[0u16; 8]; 2][..]);
+	check(&mut 0u64);
+	check(&mut [false; 37][..]);
+	check(&mut super::CountedBitSet::new(0u32));
+}
+
+#[test]
+fn test_and_count_at_least() {
+	let query = [!0u32, 0x0000_ffff, 0, 0x8000_0000];
+	let candidate = [0x0000_00ffu32, 0x0000_0f0f, !0, !0];
+	// 8 + 8 + 0 + 1 bits in common
+	let common = (0..query.bit_len()).filter(|&i| query.bit_test(i) && candidate.bit_test(i)).count();
+	assert_eq!(common, 17);
+	for threshold in 0..=common {
+		assert!(query.bit_and_count_at_least(&candidate, threshold));
+	}
+	assert!(!query.bit_and_count_at_least(&candidate, common + 1));
+	assert!(!query.bit_and_count_at_least(&[0; 4], 1));
+	assert!([0u8; 0].bit_and_count_at_least(&[], 0));
+
+	assert!(0xf0u8.bit_and_count_at_least(&0x30, 2));
+	assert!(!0xf0u8.bit_and_count_at_least(&0x30, 3));
+	assert!([[!0u64; 2]; 2].bit_and_count_at_least(&[[!0; 2]; 2], 256));
+	assert!(!super::CountedBitSet::new(0xffu16).bit_and_count_at_least(&super::CountedBitSet::new(0x1ff), 9));
+
+	// Stops visiting words once the threshold is reached
+	let query = [0x0101_0101u32; 64];
+	let candidate = [0x0000_0101u32; 64];
+	for (threshold, expected) in [(0, 0), (1, 1), (2, 1), (3, 2), (100, 50), (128, 64), (129, 64)] {
+		let mut visits = 0;
+		let counts = query.iter().zip(&candidate).map(|(&a, &b)| (a & b).count_ones()).inspect(|_| visits += 1);
+		assert_eq!(count_up_to(counts, threshold) >= threshold, threshold <= 128);
+		assert_eq!(visits, expected, "threshold={}", threshold);
+	}
+}
+
+#[test]
+fn test_missing_from() {
+	fn check<T: ?Sized + BitSet>(required: &T, available: &T, out: &mut T) {
+		required.bit_missing_from(available, out);
+		assert_eq!(out.bit_none(), required.bit_subset(available));
+		for i in 0..required.bit_len() {
+			assert_eq!(out.bit_test(i), required.bit_test(i) && !available.bit_test(i));
+		}
+	}
+	let required = [0x0000_00ffu32, 0x8000_0000];
+	check(&required[..], &[0x0000_0fff, 0x8000_0001], &mut [!0; 2]);
+	check(&required[..], &[0x0000_0f0f, 0x0000_0001], &mut [!0; 2]);
+	check(&0x0fu8, &0x1f, &mut 0);
+	check(&0x0fu8, &0x1e, &mut 0);
+	check(&[[0x5555u16; 8]; 1][..], &[[0xffff; 8]], &mut [[0; 8]]);
+	check(&[[0x5555u16; 8]; 1][..], &[[0x7777, 0x5555, 0xffff, 0x1555, !0, !0, !0, !0]], &mut [[0; 8]]);
+	check(&[[0xffu64; 2]; 1][..], &[[0xf; 2]], &mut [[0; 2]]);
+
+	let mut out = super::CountedBitSet::new(!0u32);
+	super::CountedBitSet::new(0xf0u32).bit_missing_from(&super::CountedBitSet::new(0x30), &mut out);
+	assert_eq!(out.bit_count(), 2);
+}
+
+#[test]
+fn test_shift_fill() {
+	let words = [0x8765_4321u32, 0x0fed_cba9, 0xf0f0_0f0f];
+	let mut bools = [false; 96];
+	for (i, bit) in bools.iter_mut().enumerate() {
+		*bit = words.bit_test(i);
+	}
+	for &n in &[0, 1, 32, 37, 95, 96, 200] {
+		for &fill in &[false, true] {
+			let (mut x, mut y) = (words, bools);
+			x.bit_shl_fill(n, fill);
+			y.bit_shl_fill(n, fill);
+			assert!((0..96).all(|i| x.bit_test(i) == y[i]), "shl n={} fill={}", n, fill);
+			let (mut x, mut y) = (words, bools);
+			x.bit_shr_fill(n, fill);
+			y.bit_shr_fill(n, fill);
+			assert!((0..96).all(|i| x.bit_test(i) == y[i]), "shr n={} fill={}", n, fill);
+		}
+	}
+
+	let mut x = words;
+	x.bit_shl_fill(37, true);
+	assert_eq!(x, [!0, 0xeca8_643f, 0xfdb9_7530]);
+	x.bit_shr_fill(37, false);
+	assert_eq!(x, [0x8765_4321, 0x07ed_cba9, 0]);
+
+	assert_eq!(*0x8000_0001u32.bit_shr_fill(1, true), 0xc000_0000);
+	assert_eq!(*0x8000_0001u32.bit_shl_fill(32, true), !0);
+	assert_eq!(*0x8000_0001u32.bit_shr_fill(40, false), 0);
+	assert_eq!(*[[0x8000u16; 8]][..].bit_shl_fill(1, true), [[0x0001, 1, 1, 1, 1, 1, 1, 1]]);
+	let mut counted = super::CountedBitSet::new(0x0fu8);
+	assert_eq!(counted.bit_shr_fill(2, true).bit_count(), 4);
+}
+
+#[test]
+fn test_select_high() {
+	#[track_caller]
+	fn check<T: ?Sized + BitSet>(bits: &T) {
+		let ones = (0..bits.bit_len()).filter(|&i| bits.bit_test(i)).count();
+		for k in 0..ones + 2 {
+			let expected = (0..bits.bit_len()).rev().filter(|&i| bits.bit_test(i)).nth(k);
+			assert_eq!(bits.bit_select_high(k), expected, "k={}", k);
+		}
+	}
+	let words = [0x8765_4321u32, 0, 0x0000_0001, 0x8000_0000];
+	check(&words[..]);
+	check(&[0u64; 3][..]);
+	check(&[!0u8; 3][..]);
+	check(&[0x0123_4567_89ab_cdef_0000_0000_0000_8001u128][..]);
+	check(&0x8001u16);
+	check(&[[0x0180u16, 0, 0, 0, 0, 0, 0, 0x8000]][..]);
+	check(&[true, false, true, true][..]);
+	check(&super::CountedBitSet::new(0xf0f0u16));
+	check(&super::BitArray::<100, 2>::from_words([0x1, !0]));
+	assert_eq!(words.bit_select_high(0), Some(127));
+	assert_eq!(words.bit_select_high(1), Some(64));
+	assert_eq!(words.bit_select_high(2), Some(31));
+}
+
+#[test]
+fn test_empty() {
+	#[track_caller]
+	fn check<T: ?Sized + BitSet>(bits: &T) {
+		assert_eq!(bits.bit_len(), 0);
+		assert!(bits.bit_all());
+		assert!(bits.bit_is_full());
+		assert!(!bits.bit_any());
+		assert!(bits.bit_none());
+		assert!(bits.bit_is_empty());
+		assert_eq!(bits.bit_count(), 0);
+		assert_eq!(bits.bit_summary(), super::BitSummary::default());
+		assert_eq!(bits.bit_find_first_zero(), None);
+	}
+	check(&[0u8; 0][..]);
+	check(&[0u16; 0][..]);
+	check(&[0u32; 0][..]);
+	check(&[0u64; 0][..]);
+	check(&[0u128; 0][..]);
+	check(&[false; 0][..]);
+	check(&[[0u32; 4]; 0][..]);
+	check(&[[0u64; 4]; 0][..]);
+	check(&super::BitArray::<0, 0>::new());
+	#[cfg(feature = "std")]
+	{
+		check(&std::vec::Vec::<u32>::new());
+		check(&super::CountedBitSet::new(std::vec::Vec::<u8>::new()));
+	}
+}
+
+#[test]
+fn test_hamming_at_most() {
+	#[track_caller]
+	fn check<T: ?Sized + BitSet>(a: &T, b: &T) {
+		let distance = (0..a.bit_len()).filter(|&i| a.bit_test(i) != b.bit_test(i)).count();
+		for max in 0..distance + 3 {
+			assert_eq!(a.bit_hamming_at_most(b, max), distance <= max, "max={}", max);
+		}
+	}
+	let a = [0x8765_4321u32, 0x0fed_cba9, 0, !0];
+	let b = [0x8765_4320u32, 0x0fed_cba9, 0, 0x7fff_ffff];
+	// Accepted only after scanning every word, rejected in the first word
+	assert!(a.bit_hamming_at_most(&b, 2));
+	assert!(!a.bit_hamming_at_most(&b, 1));
+	assert!(!a.bit_hamming_at_most(&[0; 4], 0));
+	check(&a[..], &b[..]);
+	check(&a[..], &[!0; 4][..]);
+	check(&0x0123_4567_89ab_cdef_0000_0000_0000_8001u128, &0);
+	check(&[[0x0180u16, 0, 0, 0, 0, 0, 0, 0x8000]][..], &[[0x0181, 0, 1, 0, 0, 0, 0, 0]]);
+	check(&[true, false, true, true][..], &[true, true, false, true]);
+	check(&super::CountedBitSet::new(0xf0f0u16), &super::CountedBitSet::new(0x0ff0));
+	check(&super::CountedBitSet::new(0xffffu16), &super::CountedBitSet::new(0x0001));
+	check(&super::BitArray::<100, 2>::from_words([0x1, !0]), &super::BitArray::new());
+
+	// Stops visiting words at the first word where the distance exceeds the bound
+	let a = [0u16; 16];
+	let mut b = [0u16; 16];
+	b[3] = 0x0003;
+	b[7] = 0x0100;
+	b[12] = 0xffff;
+	for (max, expected, within) in [(0, 4, false), (1, 4, false), (2, 8, false), (18, 13, false), (19, 16, true)] {
+		let mut visits = 0;
+		let counts = a.iter().zip(&b).map(|(&a, &b)| (a ^ b).count_ones()).inspect(|_| visits += 1);
+		assert_eq!(sum_at_most(counts, max), within);
+		assert_eq!(visits, expected, "max={}", max);
+	}
+}
+
+#[cfg(feature = "lut-popcount")]
+#[test]
+fn test_lut_popcount() {
+	for byte in 0..=255u8 {
+		assert_eq!(lut_count_u8(byte), byte.count_ones());
+		assert_eq!(lut_count_u16(byte as u16 * 0x0101), (byte as u16 * 0x0101).count_ones());
+	}
+	assert_eq!(lut_count_u16(0x8001), 2);
+	assert_eq!([0xffu8, 0x0f, 0x81][..].bit_count(), 14);
+	assert_eq!([0xffffu16, 0x00f0, 0x8000][..].bit_count(), 21);
+}
+
 // Tests whether `bit X` is the same bit regardless of underlying primitive used.
 // NOTE: This only works on little endian, nobody cares about big endian anyway.
 #[test]
@@ -179,3 +2151,37 @@ fn test_transmute() {
 		assert_eq!(uint[0], u32::from_ne_bytes(ubyte));
 	}
 }
+
+#[test]
+fn test_andnot_changed() {
+	// Subtract a growing done set from the todo set until it stops shrinking
+	let mut todo = bitset!([0u32; 2]; 1, 5, 33, 60);
+	let mut done = [0u32; 2];
+	let mut rounds = 0;
+	for bit in 0..64 {
+		done.bit_set(bit);
+		if todo.bit_andnot_changed(&done) {
+			rounds += 1;
+		}
+	}
+	assert_eq!(rounds, 4);
+	assert!(todo.bit_none());
+	assert!(!todo.bit_andnot_changed(&done));
+}
+
+#[test]
+fn test_resample() {
+	let bits = [0b1001_0110u8, 0b0000_1111];
+	let mut up = [0u8; 4];
+	bits.bit_upsample(2, &mut up);
+	assert_eq!(up, [0b0011_1100, 0b1100_0011, 0b1111_1111, 0b0000_0000]);
+
+	let mut down = [0u8; 2];
+	up.bit_downsample_or(2, &mut down);
+	assert_eq!(down, bits);
+
+	// Any set bit in a group sets the output bit
+	let mut down = [0u8; 1];
+	[0b0100_0001u8, 0b1000_0000].bit_downsample_or(2, &mut down);
+	assert_eq!(down, [0b1000_1001]);
+}
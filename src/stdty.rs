@@ -1,3 +1,5 @@
+use core::slice;
+use std::vec::Vec;
 use super::BitSet;
 
 impl<T> BitSet for std::vec::Vec<T> where [T]: BitSet {
@@ -6,3 +8,200 @@ impl<T> BitSet for std::vec::Vec<T> where [T]: BitSet {
 impl<T> BitSet for std::boxed::Box<[T]> where [T]: BitSet {
 	impl_bitset!();
 }
+
+/// Extends the BitSet API for containers which can grow.
+///
+/// Generic code requires this trait when it needs to grow the bitset, the fixed-size containers only implement `BitSet`.
+///
+/// ```
+/// use bitset_core::{BitSet, GrowableBitSet};
+///
+/// let mut bits: Vec<u32> = Vec::new();
+/// bits.bit_reserve(100);
+/// bits.bit_set_grow(40).bit_grow_to(70);
+/// assert_eq!(bits, [0, 0x100, 0]);
+/// ```
+pub trait GrowableBitSet: BitSet {
+	/// Type of the backing words.
+	type Word;
+
+	/// Reserves capacity for at least `bits` bits in total without changing the length.
+	fn bit_reserve(&mut self, bits: usize);
+	/// Grows self with zero words until it holds at least `bits` bits, never shrinks.
+	fn bit_grow_to(&mut self, bits: usize) -> &mut Self;
+	/// Sets the given bit, first growing self with zero words to fit it.
+	#[inline]
+	fn bit_set_grow(&mut self, bit: usize) -> &mut Self {
+		self.bit_grow_to(bit + 1).bit_set(bit)
+	}
+	/// Bitwise OR, first growing self with zero words to fit rhs.
+	fn bit_union_grow(&mut self, rhs: &[Self::Word]) -> &mut Self;
+	/// Appends the words and returns the number of set bits appended.
+	///
+	/// Adding the result to a running total keeps the population count up to date while streaming words in.
+	///
+	/// ```
+	/// use bitset_core::{BitSet, GrowableBitSet};
+	///
+	/// let mut bits: Vec<u64> = Vec::new();
+	/// let mut count = bits.bit_extend_words(&[0xff, 0]);
+	/// count += bits.bit_extend_words(&[1 << 63]);
+	/// assert_eq!(count, 9);
+	/// assert!(bits.bit_test(191));
+	/// ```
+	fn bit_extend_words(&mut self, words: &[Self::Word]) -> usize;
+	/// Inserts a bit at the index like `bit_insert`, first growing self with a zero word if the highest bit would be lost.
+	///
+	/// Inserting at `bit_len()` appends the bit. Panics if `index > bit_len()`.
+	fn bit_insert_grow(&mut self, index: usize, value: bool) -> &mut Self;
+}
+
+impl<T: Copy + Default> GrowableBitSet for Vec<T> where [T]: BitSet {
+	type Word = T;
+
+	#[inline]
+	fn bit_reserve(&mut self, bits: usize) {
+		let words = words_for::<T>(bits);
+		self.reserve(words.saturating_sub(self.len()));
+	}
+	#[inline]
+	fn bit_grow_to(&mut self, bits: usize) -> &mut Self {
+		let words = words_for::<T>(bits);
+		if self.len() < words {
+			self.resize(words, T::default());
+		}
+		self
+	}
+	#[inline]
+	fn bit_union_grow(&mut self, rhs: &[T]) -> &mut Self {
+		if self.len() < rhs.len() {
+			self.resize(rhs.len(), T::default());
+		}
+		self[..rhs.len()].bit_or(rhs);
+		self
+	}
+	#[inline]
+	fn bit_extend_words(&mut self, words: &[T]) -> usize {
+		self.extend_from_slice(words);
+		words.bit_count()
+	}
+	#[inline]
+	fn bit_insert_grow(&mut self, index: usize, value: bool) -> &mut Self {
+		let len = self.bit_len();
+		assert!(index <= len);
+		if index == len || self.bit_test(len - 1) {
+			self.push(T::default());
+		}
+		self.bit_insert(index, value)
+	}
+}
+
+// Number of words needed to hold the bits
+#[inline]
+fn words_for<T: Default>(bits: usize) -> usize where [T]: BitSet {
+	let word_bits = slice::from_ref(&T::default()).bit_len();
+	bits.div_ceil(word_bits)
+}
+
+/// Bitwise OR of all the items into `acc`, growing it to fit the longest item.
+///
+/// ```
+/// use bitset_core::bit_accumulate;
+///
+/// let mut acc: Vec<u32> = vec![0x1];
+/// bit_accumulate(&mut acc, &[&[0x2, 0x20], &[], &[0x4, 0x40, 0x400]]);
+/// assert_eq!(acc, [0x7, 0x60, 0x400]);
+/// ```
+#[inline]
+pub fn bit_accumulate<T: ?Sized + GrowableBitSet>(acc: &mut T, items: &[&[T::Word]]) {
+	for item in items {
+		acc.bit_union_grow(item);
+	}
+}
+
+//----------------------------------------------------------------
+
+#[test]
+fn test_grow() {
+	let mut bits: Vec<u8> = Vec::new();
+	bits.bit_reserve(100);
+	assert!(bits.is_empty() && bits.capacity() >= 13);
+	bits.bit_grow_to(9);
+	assert_eq!(bits, [0, 0]);
+	bits.bit_grow_to(3);
+	assert_eq!(bits.bit_len(), 16);
+	bits.bit_set_grow(3).bit_set_grow(16).bit_set_grow(31);
+	assert_eq!(bits, [0x08, 0, 0x01, 0x80]);
+
+	let mut bools: Vec<bool> = Vec::new();
+	bools.bit_set_grow(4);
+	assert_eq!(bools, [false, false, false, false, true]);
+	let mut simd: Vec<[u32; 4]> = Vec::new();
+	simd.bit_set_grow(128);
+	assert_eq!(simd.len(), 2);
+	assert_eq!(simd.bit_summary().first, Some(128));
+}
+
+#[test]
+fn test_accumulate() {
+	fn collect<T: GrowableBitSet<Word = u32>>(mut acc: T, items: &[&[u32]]) -> T {
+		bit_accumulate(&mut acc, items);
+		acc
+	}
+	let items: [&[u32]; 4] = [&[0x1], &[0x2, 0x20], &[], &[0x4, 0x40, 0x400]];
+	let acc = collect(Vec::new(), &items);
+	assert_eq!(acc, [0x7, 0x60, 0x400]);
+	let acc = collect(vec![0x8, 0, 0, 0x8000], &items);
+	assert_eq!(acc, [0xf, 0x60, 0x400, 0x8000]);
+	assert_eq!(collect(Vec::new(), &[]), []);
+}
+
+#[test]
+fn test_union_grow() {
+	let mut acc: Vec<u32> = Vec::new();
+	acc.bit_union_grow(&[0x1]);
+	acc.bit_union_grow(&[0x2, 0x20]);
+	acc.bit_union_grow(&[]);
+	acc.bit_union_grow(&[0x4, 0x40, 0x400]);
+	acc.bit_union_grow(&[0x8]);
+	assert_eq!(acc, [0xf, 0x60, 0x400]);
+
+	let mut acc = vec![[0u64; 2]];
+	acc.bit_union_grow(&[[1, 2], [3, 4]]);
+	assert_eq!(acc, [[1, 2], [3, 4]]);
+}
+
+#[test]
+fn test_extend_words() {
+	let mut bits: Vec<u64> = Vec::new();
+	let mut count = 0;
+	for chunk in [&[0x8000_0000_0000_0001u64][..], &[], &[!0, 0x10]].iter() {
+		count += bits.bit_extend_words(chunk);
+		assert_eq!(count, bits.bit_count());
+	}
+	assert_eq!(count, 67);
+	assert_eq!(bits.bit_len(), 192);
+	assert!(bits.bit_test(0) && bits.bit_test(63) && bits.bit_test(100) && bits.bit_test(132));
+	assert!(!bits.bit_test(131));
+
+	let mut bytes = vec![0x0fu8];
+	assert_eq!(bytes.bit_extend_words(&[0x80, 0x01]), 2);
+	assert_eq!(bytes, [0x0f, 0x80, 0x01]);
+}
+
+#[test]
+fn test_insert_grow() {
+	let mut bits: Vec<u8> = Vec::new();
+	let mut reference: Vec<bool> = Vec::new();
+	let mut random = super::xorshift(0x2545_f491);
+	for _ in 0..200 {
+		let seed = random();
+		let index = seed as usize % (reference.len() + 1);
+		let value = seed & 0x8000_0000 != 0;
+		bits.bit_insert_grow(index, value);
+		reference.insert(index, value);
+	}
+	assert!((0..reference.len()).all(|i| bits.bit_test(i) == reference[i]));
+	assert!((reference.len()..bits.bit_len()).all(|i| !bits.bit_test(i)));
+	assert_eq!(bits.len(), 25);
+}
@@ -1,4 +1,7 @@
-use super::BitSet;
+use core::{cmp, ops, slice};
+use core::num::Wrapping;
+use super::slice::vote;
+use super::{BitOp, BitSet, BitSummary, Endian, LengthError};
 
 macro_rules! impl_bit_set_uint {
 	($ty:ty, $bits_per_word:literal) => {
@@ -14,30 +17,78 @@ macro_rules! impl_bit_set_uint {
 			}
 			#[inline]
 			fn bit_test(&self, bit: usize) -> bool {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
 				*self & (1 << bit as u32) != 0
 			}
 			#[inline]
 			fn bit_set(&mut self, bit: usize) -> &mut Self {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
 				*self |= 1 << bit as u32;
 				self
 			}
 			#[inline]
 			fn bit_reset(&mut self, bit: usize) -> &mut Self {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
 				*self &= !(1 << bit as u32);
 				self
 			}
 			#[inline]
 			fn bit_flip(&mut self, bit: usize) -> &mut Self {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
 				*self ^= 1 << bit as u32;
 				self
 			}
 			#[inline]
 			fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
 				let mask = 1 << bit as u32;
 				*self = (*self & !mask) | (<$ty>::wrapping_add(!(value as $ty), 1) & mask);
 				self
 			}
 			#[inline]
+			fn bit_test_and_set(&mut self, bit: usize) -> bool {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
+				let mask = 1 << bit as u32;
+				let old = *self & mask != 0;
+				*self |= mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
+				let mask = 1 << bit as u32;
+				let old = *self & mask != 0;
+				*self &= !mask;
+				old
+			}
+			#[inline]
+			fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+				debug_assert!(bit < $bits_per_word, "bit index out of range");
+				let mask = 1 << bit as u32;
+				let old = *self & mask != 0;
+				*self ^= mask;
+				old
+			}
+			#[inline]
+			fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+				slice::from_mut(self).bit_cond_range(range, value);
+				self
+			}
+			#[inline]
+			fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+				slice::from_mut(self).bit_set_bits(indices);
+				self
+			}
+			#[inline]
+			fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+				slice::from_ref(self).bit_test_at(word, bit_in_word)
+			}
+			#[inline]
+			fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+				slice::from_mut(self).bit_set_at(word, bit_in_word);
+				self
+			}
+			#[inline]
 			fn bit_all(&self) -> bool {
 				*self == !0
 			}
@@ -50,6 +101,22 @@ macro_rules! impl_bit_set_uint {
 				*self == 0
 			}
 			#[inline]
+			fn bit_is_empty(&self) -> bool {
+				*self == 0
+			}
+			#[inline]
+			fn bit_is_full(&self) -> bool {
+				*self == !0
+			}
+			#[inline]
+			fn bit_all_len(&self, len: usize) -> bool {
+				slice::from_ref(self).bit_all_len(len)
+			}
+			#[inline]
+			fn bit_any_len(&self, len: usize) -> bool {
+				slice::from_ref(self).bit_any_len(len)
+			}
+			#[inline]
 			fn bit_eq(&self, rhs: &Self) -> bool {
 				*self == *rhs
 			}
@@ -58,6 +125,10 @@ macro_rules! impl_bit_set_uint {
 				*self & *rhs == 0
 			}
 			#[inline]
+			fn bit_disjoint_range(&self, rhs: &Self, range: ops::Range<usize>) -> bool {
+				slice::from_ref(self).bit_disjoint_range(slice::from_ref(rhs), range)
+			}
+			#[inline]
 			fn bit_subset(&self, rhs: &Self) -> bool {
 				*self | *rhs == *rhs
 			}
@@ -66,6 +137,18 @@ macro_rules! impl_bit_set_uint {
 				*self | *rhs == *self
 			}
 			#[inline]
+			fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				(*self ^ *rhs) & *mask == 0
+			}
+			#[inline]
+			fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				*self & !*rhs & *mask == 0
+			}
+			#[inline]
+			fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+				*out = *self & !*available;
+			}
+			#[inline]
 			fn bit_or(&mut self, rhs: &Self) -> &mut Self {
 				*self |= *rhs;
 				self
@@ -81,6 +164,29 @@ macro_rules! impl_bit_set_uint {
 				self
 			}
 			#[inline]
+			fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+				slice::from_mut(self).bit_andnot_range(slice::from_ref(rhs), range);
+				self
+			}
+			#[inline]
+			fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+				let changed = *self & *rhs;
+				*self &= !*rhs;
+				changed != 0
+			}
+			#[inline]
+			fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+				let delta = (!*self & *rhs).count_ones() as usize;
+				*self |= *rhs;
+				delta
+			}
+			#[inline]
+			fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+				let delta = (*self & *rhs).count_ones() as usize;
+				*self &= !*rhs;
+				delta
+			}
+			#[inline]
 			fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
 				*self ^= *rhs;
 				self
@@ -91,14 +197,215 @@ macro_rules! impl_bit_set_uint {
 				self
 			}
 			#[inline]
+			fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+				*self ^= (0 as $ty).wrapping_sub(condition as $ty);
+				self
+			}
+			#[inline]
 			fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
 				*self = *self & !*mask | *rhs & *mask;
 				self
 			}
 			#[inline]
+			fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+				*self = *self & *mask | *rhs & !*mask;
+				self
+			}
+			#[inline]
+			fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+				*self |= *rhs & *cond;
+				self
+			}
+			#[inline]
+			fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+				*self = *a & !*mask | *b & *mask;
+				self
+			}
+			#[inline]
+			fn bit_vote_into(&mut self, inputs: &[&Self], threshold: usize) -> &mut Self {
+				*self = vote(inputs.iter().map(|&&input| input), inputs.len(), threshold);
+				self
+			}
+			#[inline]
+			fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+				*self |= word as $ty;
+				self
+			}
+			#[inline]
+			fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+				*self &= word as $ty;
+				self
+			}
+			#[inline]
+			fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+				*self ^= word as $ty;
+				self
+			}
+			#[inline]
+			fn bit_rotate_left(&mut self, n: usize) -> &mut Self {
+				*self = self.rotate_left((n % $bits_per_word) as u32);
+				self
+			}
+			#[inline]
+			fn bit_rotate_right(&mut self, n: usize) -> &mut Self {
+				*self = self.rotate_right((n % $bits_per_word) as u32);
+				self
+			}
+			#[inline]
+			fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+				slice::from_mut(self).bit_insert(index, value);
+				self
+			}
+			#[inline]
+			fn bit_remove(&mut self, index: usize) -> bool {
+				slice::from_mut(self).bit_remove(index)
+			}
+			#[inline]
+			fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+				slice::from_mut(self).bit_shl_len(n, len);
+				self
+			}
+			#[inline]
+			fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				let fill = if fill { !0 } else { 0 };
+				*self = if n < $bits_per_word { *self << n | fill & !(!0 << n) } else { fill };
+				self
+			}
+			#[inline]
+			fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				let fill: $ty = if fill { !0 } else { 0 };
+				*self = if n < $bits_per_word { *self >> n | fill & !(!0 >> n) } else { fill };
+				self
+			}
+			#[inline]
+			fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+				slice::from_mut(self).bit_reverse_range(range);
+				self
+			}
+			#[inline]
+			fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+				slice::from_mut(self).bit_reverse_groups(group_bits);
+				self
+			}
+			#[inline]
+			fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+				slice::from_mut(self).bit_swap_ranges(a, b, len);
+				self
+			}
+			#[inline]
+			fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+				*self = match op {
+					BitOp::Or => *a | *b,
+					BitOp::And => *a & *b,
+					BitOp::AndNot => *a & !*b,
+					BitOp::Xor => *a ^ *b,
+				};
+				self
+			}
+			#[inline]
+			fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+				slice::from_mut(self).bit_and_tiled(slice::from_ref(pattern));
+				self
+			}
+			#[inline]
+			fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+				slice::from_mut(self).bit_or_tiled(slice::from_ref(pattern));
+				self
+			}
+			#[inline]
+			fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+				slice::from_mut(self).bit_xor_tiled(slice::from_ref(pattern));
+				self
+			}
+			#[inline]
+			fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.bit_or(rhs)
+			}
+			#[inline]
+			fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.bit_and(rhs)
+			}
+			#[inline]
+			fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.bit_xor(rhs)
+			}
+			#[inline]
 			fn bit_count(&self) -> usize {
 				self.count_ones() as usize
 			}
+			#[inline]
+			fn bit_count_up_to(&self, limit: usize) -> usize {
+				cmp::min(self.count_ones() as usize, limit)
+			}
+			#[inline]
+			fn bit_andnot_count(&self, rhs: &Self) -> usize {
+				slice::from_ref(self).bit_andnot_count(slice::from_ref(rhs))
+			}
+			#[inline]
+			fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+				slice::from_ref(self).bit_and_count_at_least(slice::from_ref(rhs), threshold)
+			}
+			#[inline]
+			fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+				slice::from_ref(self).bit_hamming_at_most(slice::from_ref(rhs), max)
+			}
+			#[inline]
+			fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+				slice::from_ref(self).bit_weighted_overlap(slice::from_ref(rhs), weights)
+			}
+			#[inline]
+			fn bit_leading_ones(&self) -> usize {
+				self.leading_ones() as usize
+			}
+			#[inline]
+			fn bit_trailing_ones(&self) -> usize {
+				self.trailing_ones() as usize
+			}
+			#[inline]
+			fn bit_find_first_zero(&self) -> Option<usize> {
+				let word = !*self;
+				if word != 0 { Some(word.trailing_zeros() as usize) } else { None }
+			}
+			#[inline]
+			fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+				if from >= $bits_per_word {
+					return None;
+				}
+				let word = !*self & (!0 << from);
+				if word != 0 { Some(word.trailing_zeros() as usize) } else { None }
+			}
+			#[inline]
+			fn bit_select_high(&self, k: usize) -> Option<usize> {
+				slice::from_ref(self).bit_select_high(k)
+			}
+			#[inline]
+			fn bit_summary(&self) -> BitSummary {
+				slice::from_ref(self).bit_summary()
+			}
+			#[inline]
+			fn bit_rank_table(&self, out: &mut [usize]) {
+				slice::from_ref(self).bit_rank_table(out)
+			}
+			#[inline]
+			fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+				slice::from_mut(self).bit_load_bytes(bytes, endian)
+			}
+			#[inline]
+			fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+				slice::from_ref(self).bit_store_bytes(out, endian)
+			}
+			#[inline]
+			fn bit_nonzero_words(&self) -> usize {
+				(*self != 0) as usize
+			}
+			#[inline]
+			fn bit_first_nonzero_word(&self) -> Option<usize> {
+				if *self != 0 { Some(0) } else { None }
+			}
+			#[inline]
+			fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+				slice::from_ref(self).bit_fold_words(init, f)
+			}
 		}
 	};
 }
@@ -109,6 +416,386 @@ impl_bit_set_uint!(u32, 32);
 impl_bit_set_uint!(u64, 64);
 impl_bit_set_uint!(u128, 128);
 
+macro_rules! impl_bit_set_wrapping {
+	($ty:ty) => {
+		impl BitSet for Wrapping<$ty> {
+			#[inline]
+			fn bit_len(&self) -> usize {
+				self.0.bit_len()
+			}
+			#[inline]
+			fn bit_init(&mut self, value: bool) -> &mut Self {
+				self.0.bit_init(value);
+				self
+			}
+			#[inline]
+			fn bit_test(&self, bit: usize) -> bool {
+				self.0.bit_test(bit)
+			}
+			#[inline]
+			fn bit_set(&mut self, bit: usize) -> &mut Self {
+				self.0.bit_set(bit);
+				self
+			}
+			#[inline]
+			fn bit_reset(&mut self, bit: usize) -> &mut Self {
+				self.0.bit_reset(bit);
+				self
+			}
+			#[inline]
+			fn bit_flip(&mut self, bit: usize) -> &mut Self {
+				self.0.bit_flip(bit);
+				self
+			}
+			#[inline]
+			fn bit_cond(&mut self, bit: usize, value: bool) -> &mut Self {
+				self.0.bit_cond(bit, value);
+				self
+			}
+			#[inline]
+			fn bit_test_and_set(&mut self, bit: usize) -> bool {
+				self.0.bit_test_and_set(bit)
+			}
+			#[inline]
+			fn bit_test_and_reset(&mut self, bit: usize) -> bool {
+				self.0.bit_test_and_reset(bit)
+			}
+			#[inline]
+			fn bit_test_and_flip(&mut self, bit: usize) -> bool {
+				self.0.bit_test_and_flip(bit)
+			}
+			#[inline]
+			fn bit_cond_range(&mut self, range: ops::Range<usize>, value: bool) -> &mut Self {
+				self.0.bit_cond_range(range, value);
+				self
+			}
+			#[inline]
+			fn bit_set_bits(&mut self, indices: &[usize]) -> &mut Self {
+				self.0.bit_set_bits(indices);
+				self
+			}
+			#[inline]
+			fn bit_test_at(&self, word: usize, bit_in_word: u32) -> bool {
+				self.0.bit_test_at(word, bit_in_word)
+			}
+			#[inline]
+			fn bit_set_at(&mut self, word: usize, bit_in_word: u32) -> &mut Self {
+				self.0.bit_set_at(word, bit_in_word);
+				self
+			}
+			#[inline]
+			fn bit_all(&self) -> bool {
+				self.0.bit_all()
+			}
+			#[inline]
+			fn bit_any(&self) -> bool {
+				self.0.bit_any()
+			}
+			#[inline]
+			fn bit_none(&self) -> bool {
+				self.0.bit_none()
+			}
+			#[inline]
+			fn bit_is_empty(&self) -> bool {
+				self.0.bit_is_empty()
+			}
+			#[inline]
+			fn bit_is_full(&self) -> bool {
+				self.0.bit_is_full()
+			}
+			#[inline]
+			fn bit_all_len(&self, len: usize) -> bool {
+				self.0.bit_all_len(len)
+			}
+			#[inline]
+			fn bit_any_len(&self, len: usize) -> bool {
+				self.0.bit_any_len(len)
+			}
+			#[inline]
+			fn bit_eq(&self, rhs: &Self) -> bool {
+				self.0.bit_eq(&rhs.0)
+			}
+			#[inline]
+			fn bit_disjoint(&self, rhs: &Self) -> bool {
+				self.0.bit_disjoint(&rhs.0)
+			}
+			#[inline]
+			fn bit_disjoint_range(&self, rhs: &Self, range: ops::Range<usize>) -> bool {
+				self.0.bit_disjoint_range(&rhs.0, range)
+			}
+			#[inline]
+			fn bit_subset(&self, rhs: &Self) -> bool {
+				self.0.bit_subset(&rhs.0)
+			}
+			#[inline]
+			fn bit_superset(&self, rhs: &Self) -> bool {
+				self.0.bit_superset(&rhs.0)
+			}
+			#[inline]
+			fn bit_eq_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				self.0.bit_eq_masked(&rhs.0, &mask.0)
+			}
+			#[inline]
+			fn bit_subset_masked(&self, rhs: &Self, mask: &Self) -> bool {
+				self.0.bit_subset_masked(&rhs.0, &mask.0)
+			}
+			#[inline]
+			fn bit_missing_from(&self, available: &Self, out: &mut Self) {
+				self.0.bit_missing_from(&available.0, &mut out.0)
+			}
+			#[inline]
+			fn bit_or(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_or(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_and(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_and(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_andnot(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_andnot(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_andnot_range(&mut self, rhs: &Self, range: ops::Range<usize>) -> &mut Self {
+				self.0.bit_andnot_range(&rhs.0, range);
+				self
+			}
+			#[inline]
+			fn bit_andnot_changed(&mut self, rhs: &Self) -> bool {
+				self.0.bit_andnot_changed(&rhs.0)
+			}
+			#[inline]
+			fn bit_or_delta(&mut self, rhs: &Self) -> usize {
+				self.0.bit_or_delta(&rhs.0)
+			}
+			#[inline]
+			fn bit_andnot_delta(&mut self, rhs: &Self) -> usize {
+				self.0.bit_andnot_delta(&rhs.0)
+			}
+			#[inline]
+			fn bit_xor(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_xor(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_not(&mut self) -> &mut Self {
+				self.0.bit_not();
+				self
+			}
+			#[inline]
+			fn bit_not_cond(&mut self, condition: bool) -> &mut Self {
+				self.0.bit_not_cond(condition);
+				self
+			}
+			#[inline]
+			fn bit_mask(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+				self.0.bit_mask(&rhs.0, &mask.0);
+				self
+			}
+			#[inline]
+			fn bit_mask_inv(&mut self, rhs: &Self, mask: &Self) -> &mut Self {
+				self.0.bit_mask_inv(&rhs.0, &mask.0);
+				self
+			}
+			#[inline]
+			fn bit_or_and(&mut self, rhs: &Self, cond: &Self) -> &mut Self {
+				self.0.bit_or_and(&rhs.0, &cond.0);
+				self
+			}
+			#[inline]
+			fn bit_blend_into(&mut self, a: &Self, b: &Self, mask: &Self) -> &mut Self {
+				self.0.bit_blend_into(&a.0, &b.0, &mask.0);
+				self
+			}
+			#[inline]
+			fn bit_vote_into(&mut self, inputs: &[&Self], threshold: usize) -> &mut Self {
+				self.0 = vote(inputs.iter().map(|input| input.0), inputs.len(), threshold);
+				self
+			}
+			#[inline]
+			fn bit_or_broadcast(&mut self, word: u64) -> &mut Self {
+				self.0.bit_or_broadcast(word);
+				self
+			}
+			#[inline]
+			fn bit_and_broadcast(&mut self, word: u64) -> &mut Self {
+				self.0.bit_and_broadcast(word);
+				self
+			}
+			#[inline]
+			fn bit_xor_broadcast(&mut self, word: u64) -> &mut Self {
+				self.0.bit_xor_broadcast(word);
+				self
+			}
+			#[inline]
+			fn bit_rotate_left(&mut self, n: usize) -> &mut Self {
+				self.0.bit_rotate_left(n);
+				self
+			}
+			#[inline]
+			fn bit_rotate_right(&mut self, n: usize) -> &mut Self {
+				self.0.bit_rotate_right(n);
+				self
+			}
+			#[inline]
+			fn bit_insert(&mut self, index: usize, value: bool) -> &mut Self {
+				self.0.bit_insert(index, value);
+				self
+			}
+			#[inline]
+			fn bit_remove(&mut self, index: usize) -> bool {
+				self.0.bit_remove(index)
+			}
+			#[inline]
+			fn bit_shl_len(&mut self, n: usize, len: usize) -> &mut Self {
+				self.0.bit_shl_len(n, len);
+				self
+			}
+			#[inline]
+			fn bit_shl_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				self.0.bit_shl_fill(n, fill);
+				self
+			}
+			#[inline]
+			fn bit_shr_fill(&mut self, n: usize, fill: bool) -> &mut Self {
+				self.0.bit_shr_fill(n, fill);
+				self
+			}
+			#[inline]
+			fn bit_reverse_range(&mut self, range: ops::Range<usize>) -> &mut Self {
+				self.0.bit_reverse_range(range);
+				self
+			}
+			#[inline]
+			fn bit_reverse_groups(&mut self, group_bits: usize) -> &mut Self {
+				self.0.bit_reverse_groups(group_bits);
+				self
+			}
+			#[inline]
+			fn bit_swap_ranges(&mut self, a: usize, b: usize, len: usize) -> &mut Self {
+				self.0.bit_swap_ranges(a, b, len);
+				self
+			}
+			#[inline]
+			fn bit_combine_into(&mut self, a: &Self, b: &Self, op: BitOp) -> &mut Self {
+				self.0.bit_combine_into(&a.0, &b.0, op);
+				self
+			}
+			#[inline]
+			fn bit_and_tiled(&mut self, pattern: &Self) -> &mut Self {
+				self.0.bit_and_tiled(&pattern.0);
+				self
+			}
+			#[inline]
+			fn bit_or_tiled(&mut self, pattern: &Self) -> &mut Self {
+				self.0.bit_or_tiled(&pattern.0);
+				self
+			}
+			#[inline]
+			fn bit_xor_tiled(&mut self, pattern: &Self) -> &mut Self {
+				self.0.bit_xor_tiled(&pattern.0);
+				self
+			}
+			#[inline]
+			fn bit_or_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_or_word_set(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_and_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_and_word_set(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_xor_word_set(&mut self, rhs: &Self) -> &mut Self {
+				self.0.bit_xor_word_set(&rhs.0);
+				self
+			}
+			#[inline]
+			fn bit_count(&self) -> usize {
+				self.0.bit_count()
+			}
+			#[inline]
+			fn bit_count_up_to(&self, limit: usize) -> usize {
+				self.0.bit_count_up_to(limit)
+			}
+			#[inline]
+			fn bit_andnot_count(&self, rhs: &Self) -> usize {
+				self.0.bit_andnot_count(&rhs.0)
+			}
+			#[inline]
+			fn bit_and_count_at_least(&self, rhs: &Self, threshold: usize) -> bool {
+				self.0.bit_and_count_at_least(&rhs.0, threshold)
+			}
+			#[inline]
+			fn bit_hamming_at_most(&self, rhs: &Self, max: usize) -> bool {
+				self.0.bit_hamming_at_most(&rhs.0, max)
+			}
+			#[inline]
+			fn bit_weighted_overlap(&self, rhs: &Self, weights: &[u32]) -> u64 {
+				self.0.bit_weighted_overlap(&rhs.0, weights)
+			}
+			#[inline]
+			fn bit_leading_ones(&self) -> usize {
+				self.0.bit_leading_ones()
+			}
+			#[inline]
+			fn bit_trailing_ones(&self) -> usize {
+				self.0.bit_trailing_ones()
+			}
+			#[inline]
+			fn bit_find_first_zero(&self) -> Option<usize> {
+				self.0.bit_find_first_zero()
+			}
+			#[inline]
+			fn bit_find_next_zero(&self, from: usize) -> Option<usize> {
+				self.0.bit_find_next_zero(from)
+			}
+			#[inline]
+			fn bit_select_high(&self, k: usize) -> Option<usize> {
+				self.0.bit_select_high(k)
+			}
+			#[inline]
+			fn bit_summary(&self) -> BitSummary {
+				self.0.bit_summary()
+			}
+			#[inline]
+			fn bit_rank_table(&self, out: &mut [usize]) {
+				self.0.bit_rank_table(out)
+			}
+			#[inline]
+			fn bit_load_bytes(&mut self, bytes: &[u8], endian: Endian) -> Result<(), LengthError> {
+				self.0.bit_load_bytes(bytes, endian)
+			}
+			#[inline]
+			fn bit_store_bytes(&self, out: &mut [u8], endian: Endian) -> Result<(), LengthError> {
+				self.0.bit_store_bytes(out, endian)
+			}
+			#[inline]
+			fn bit_nonzero_words(&self) -> usize {
+				self.0.bit_nonzero_words()
+			}
+			#[inline]
+			fn bit_first_nonzero_word(&self) -> Option<usize> {
+				self.0.bit_first_nonzero_word()
+			}
+			#[inline]
+			fn bit_fold_words<B, F: FnMut(B, usize, u64) -> B>(&self, init: B, f: F) -> B {
+				self.0.bit_fold_words(init, f)
+			}
+		}
+	};
+}
+
+impl_bit_set_wrapping!(u8);
+impl_bit_set_wrapping!(u16);
+impl_bit_set_wrapping!(u32);
+impl_bit_set_wrapping!(u64);
+impl_bit_set_wrapping!(u128);
+
 //----------------------------------------------------------------
 
 #[test]
@@ -123,3 +810,73 @@ fn tests() {
 	super::unary_tests(&mut dwords);
 	super::unary_tests(&mut qwords);
 }
+
+#[test]
+fn test_ones() {
+	assert_eq!(0xf000_00ffu32.bit_leading_ones(), 4);
+	assert_eq!(0xf000_00ffu32.bit_trailing_ones(), 8);
+	assert_eq!(0u8.bit_trailing_ones(), 0);
+	assert_eq!((!0u64).bit_leading_ones(), 64);
+}
+
+#[test]
+fn test_rotate() {
+	assert_eq!(*0x1u32.bit_rotate_left(4), 0x10);
+	assert_eq!(*0x1u32.bit_rotate_right(4), 0x1000_0000);
+	assert_eq!(*0x1234_5678u32.bit_rotate_left(32), 0x1234_5678);
+	assert_eq!(*0x1234_5678u32.bit_rotate_left(36), 0x2345_6781);
+	assert_eq!(*0x1234_5678u32.bit_rotate_right(68), 0x8123_4567);
+	assert_eq!(*0x81u8.bit_rotate_left(1), 0x03);
+	assert_eq!(*(1u128 << 127).bit_rotate_left(130), 2);
+	assert_eq!(Wrapping(0x8000_0000_0000_0000u64).bit_rotate_left(1).0, 1);
+
+	// Same as the generic rotation of the whole range
+	let mut bits = [0x1234_5678u32];
+	bits.bit_rotate_left(13);
+	assert_eq!(bits, [0x1234_5678u32.rotate_left(13)]);
+	bits.bit_rotate_right(13);
+	assert_eq!(bits, [0x1234_5678]);
+}
+
+#[test]
+fn test_bit_range() {
+	let mut word = 0u8;
+	word.bit_set(7).bit_flip(0);
+	assert_eq!(word, 0x81);
+	assert!(word.bit_test(7));
+	assert!(!word.bit_get(40));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "bit index out of range")]
+fn test_bit_out_of_range() {
+	0u8.bit_set(40);
+}
+
+#[test]
+fn test_wrapping() {
+	let mut flags = Wrapping(0u32);
+	flags.bit_set(3);
+	assert_eq!(flags, Wrapping(8));
+	assert!(flags.bit_test(3));
+	assert_eq!(flags.bit_len(), 32);
+
+	super::unary_tests(&mut Wrapping(0u8));
+	super::unary_tests(&mut Wrapping(0u128));
+}
+
+#[test]
+fn test_checked() {
+	let mut dwords = 0u32;
+	dwords.bit_set_checked(31).bit_set_checked(32).bit_set_checked(64);
+	assert_eq!(dwords, 0x8000_0000);
+	assert!(dwords.bit_get(31));
+	assert!(!dwords.bit_get(32));
+	assert!(!dwords.bit_get(64));
+
+	dwords.bit_init(true);
+	dwords.bit_reset_checked(64).bit_reset_checked(32).bit_reset_checked(31);
+	assert_eq!(dwords, 0x7fff_ffff);
+	assert!(!dwords.bit_get(usize::MAX));
+}